@@ -1,4 +1,6 @@
-use crate::{Day, Solution};
+use std::fmt::Write;
+
+use crate::{Day, Solution, Variant, rng::Rng};
 
 pub struct Day01;
 
@@ -93,6 +95,79 @@ impl Day for Day01 {
 
         Solution::Int(zeroes)
     }
+
+    fn variants(&self) -> Vec<Variant> {
+        vec![
+            Variant {
+                name: "math",
+                part: 1,
+                solve: part1_math,
+            },
+            Variant {
+                name: "math",
+                part: 2,
+                solve: part2_math,
+            },
+        ]
+    }
+
+    fn generate_input(&self, seed: u64, size: usize) -> Option<String> {
+        let mut rng = Rng::new(seed);
+        let mut input = String::new();
+
+        for _ in 0..size {
+            let direction = if rng.bool() { 'L' } else { 'R' };
+            writeln!(input, "{direction}{}", rng.range(1..=250)).unwrap();
+        }
+
+        Some(input)
+    }
+}
+
+// Same answers as the Dial, computed per rotation instead of click by click.
+fn part1_math(input: &str) -> Solution {
+    let mut position = 50i64;
+
+    Solution::Int(
+        input
+            .trim()
+            .lines()
+            .map(Rotation::parse)
+            .filter(|&r| {
+                position = match r {
+                    Rotation::Left(n) => position - n as i64,
+                    Rotation::Right(n) => position + n as i64,
+                }
+                .rem_euclid(100);
+                position == 0
+            })
+            .count() as i64,
+    )
+}
+
+fn part2_math(input: &str) -> Solution {
+    let mut position = 50i64;
+    let mut zeroes = 0;
+
+    for rotation in input.trim().lines().map(Rotation::parse) {
+        match rotation {
+            Rotation::Right(n) => {
+                zeroes += (position + n as i64) / 100;
+                position = (position + n as i64) % 100;
+            }
+            Rotation::Left(n) => {
+                let n = n as i64;
+                if position == 0 {
+                    zeroes += n / 100;
+                } else if n >= position {
+                    zeroes += (n - position) / 100 + 1;
+                }
+                position = (position - n).rem_euclid(100);
+            }
+        }
+    }
+
+    Solution::Int(zeroes)
 }
 
 #[cfg(test)]
@@ -1,7 +1,7 @@
 use rayon::prelude::*;
 use std::ops::RangeInclusive;
 
-use crate::{Day, Solution};
+use crate::{Day, Solution, Variant, rng::Rng};
 
 pub struct Day02;
 
@@ -23,6 +23,64 @@ impl Day for Day02 {
                 .sum::<i64>(),
         )
     }
+
+    fn variants(&self) -> Vec<Variant> {
+        vec![
+            Variant {
+                name: "naive",
+                part: 1,
+                solve: |input| sum_invalid_naive(input, |n| n == 2),
+            },
+            Variant {
+                name: "naive",
+                part: 2,
+                solve: |input| sum_invalid_naive(input, |n| n >= 2),
+            },
+        ]
+    }
+
+    fn generate_input(&self, seed: u64, size: usize) -> Option<String> {
+        let mut rng = Rng::new(seed);
+
+        let ranges: Vec<String> = (0..size.max(1))
+            .map(|_| {
+                let digits = rng.range(1..=10) as u32;
+                let start = rng.range(10i64.pow(digits - 1)..=10i64.pow(digits) - 1);
+                let end = start + rng.range(0..=200);
+
+                format!("{start}-{end}")
+            })
+            .collect();
+
+        Some(ranges.join(","))
+    }
+}
+
+/// Sums the IDs made of `repeats` copies of a block, for some accepted
+/// number of repeats, by comparing every possible block.
+fn sum_invalid_naive(input: &str, accept_repeats: fn(usize) -> bool) -> Solution {
+    let is_invalid = |x: i64| {
+        let s = x.to_string();
+        let bytes = s.as_bytes();
+
+        (1..bytes.len()).any(|k| {
+            bytes.len().is_multiple_of(k)
+                && accept_repeats(bytes.len() / k)
+                && bytes.chunks(k).all(|chunk| chunk == &bytes[..k])
+        })
+    };
+
+    Solution::Int(
+        input
+            .trim()
+            .split(',')
+            .flat_map(|range| {
+                let (a, b) = range.split_once('-').unwrap();
+                a.parse::<i64>().unwrap()..=b.parse().unwrap()
+            })
+            .filter(|&x| is_invalid(x))
+            .sum(),
+    )
 }
 
 fn parse_ranges(input: &str) -> impl ParallelIterator<Item = RangeInclusive<i64>> {
@@ -50,19 +108,7 @@ fn is_invalid2(x: i64) -> bool {
     let mut buffer = itoa::Buffer::new();
     let s = buffer.format(x);
 
-    let bytes = s.as_bytes();
-    let n = bytes.len();
-
-    let z = crate::search::z_algorithm(bytes);
-
-    // Try every prefix length that divides n
-    for k in 1..=n / 2 {
-        if n % k == 0 && z[k] == n - k {
-            return true;
-        }
-    }
-
-    false
+    crate::util::search::is_repetition_of_block(s.as_bytes())
 }
 
 #[cfg(test)]
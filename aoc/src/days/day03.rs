@@ -1,4 +1,4 @@
-use crate::{Day, Solution};
+use crate::{Day, Solution, util::grid2d::Grid2D};
 
 pub struct Day03;
 
@@ -7,7 +7,7 @@ impl Day for Day03 {
         let grid = parse_input(input);
 
         let total: i64 = grid
-            .into_iter()
+            .rows()
             .map(|line| {
                 let mut max_left = line[0];
                 let mut best = 0i64;
@@ -33,7 +33,7 @@ impl Day for Day03 {
         let grid = parse_input(input);
 
         let total: i64 = grid
-            .into_iter()
+            .rows()
             .map(|line| {
                 let n = line.len();
                 let k = 12;
@@ -64,12 +64,8 @@ impl Day for Day03 {
     }
 }
 
-fn parse_input(input: &str) -> Vec<Vec<u32>> {
-    input
-        .trim()
-        .lines()
-        .map(|l| l.chars().map(|c| c.to_digit(10).unwrap()).collect())
-        .collect()
+fn parse_input(input: &str) -> Grid2D<u32> {
+    Grid2D::parse_with(input.trim(), |_, _, ch| ch.to_digit(10).ok_or(ch)).unwrap()
 }
 
 #[cfg(test)]
@@ -2,7 +2,15 @@ use std::collections::VecDeque;
 
 use rustc_hash::FxHashMap;
 
-use crate::{Day, Solution, grid2d::Grid2D};
+use crate::{
+    Day, Diagnostic, Solution, Variant,
+    util::{
+        grid2d::{Connectivity, Grid2D},
+        point::{Point, Vec2},
+        prefix_sum::PrefixSum2D,
+    },
+    validate,
+};
 
 pub struct Day04;
 
@@ -29,71 +37,112 @@ impl Day for Day04 {
     }
 
     fn part2(&self, input: &str) -> Solution {
-        const NEIGHBORS: [(isize, isize); 8] = [
-            (-1, -1),
-            (-1, 0),
-            (-1, 1),
-            (1, -1),
-            (1, 0),
-            (1, 1),
-            (0, 1),
-            (0, -1),
-        ];
         let grid: Grid2D<char> = Grid2D::from(input.trim());
 
-        let mut rolls: FxHashMap<(usize, usize), usize> = FxHashMap::default();
-        let mut queue: VecDeque<(usize, usize)> = VecDeque::new();
-
-        grid.iter().for_each(|((x, y), &cell)| {
-            if cell == '@' {
-                let neighbors_count = grid
-                    .all_neighbors(x, y)
-                    .iter()
-                    .filter(|&&(_, &neighbor)| neighbor == '@')
-                    .count();
-
-                if neighbors_count < 4 {
-                    queue.push_back((x, y));
+        let (stable, _) =
+            grid.run_until_stable_incremental(Connectivity::All, |&cell, neighbors| {
+                if cell == '@' && neighbors.iter().filter(|&&&n| n == '@').count() < 4 {
+                    '.'
                 } else {
-                    rolls.insert((x, y), neighbors_count);
+                    cell
                 }
-            }
-        });
+            });
+
+        Solution::Int((count_rolls(&grid) - count_rolls(&stable)) as i64)
+    }
+
+    fn variants(&self) -> Vec<Variant> {
+        vec![
+            Variant {
+                name: "prefix_sum",
+                part: 1,
+                solve: part1_prefix_sum,
+            },
+            Variant {
+                name: "queue",
+                part: 2,
+                solve: part2_queue,
+            },
+        ]
+    }
+
+    fn validate(&self, input: &str) -> Option<Vec<Diagnostic>> {
+        Some(validate::check_grid(input, ".@"))
+    }
+}
+
+fn count_rolls(grid: &Grid2D<char>) -> usize {
+    grid.iter().filter(|&(_, &cell)| cell == '@').count()
+}
+
+/// Counts the rolls in the 3x3 square around each roll with a summed-area
+/// table.
+fn part1_prefix_sum(input: &str) -> Solution {
+    let grid: Grid2D<char> = Grid2D::from(input.trim());
+    let sums = PrefixSum2D::from_grid(&grid.map(|&cell| (cell == '@') as i64));
+
+    let count = grid
+        .iter()
+        .filter(|&(pos, &cell)| {
+            let center = Point::from(pos);
+            cell == '@' && sums.sum(center - Vec2::new(1, 1), center + Vec2::new(1, 1)) - 1 < 4
+        })
+        .count();
+
+    Solution::Int(count as i64)
+}
+
+/// Removes rolls one at a time, keeping the neighbor count of the others
+/// up to date.
+fn part2_queue(input: &str) -> Solution {
+    let grid: Grid2D<char> = Grid2D::from(input.trim());
 
-        let mut count = 0i64;
-        while !queue.is_empty() {
-            let (x, y) = queue.pop_front().unwrap();
-            count += 1;
+    let mut rolls: FxHashMap<(usize, usize), usize> = FxHashMap::default();
+    let mut queue: VecDeque<(usize, usize)> = VecDeque::new();
 
-            NEIGHBORS
+    grid.iter().for_each(|((x, y), &cell)| {
+        if cell == '@' {
+            let neighbors_count = grid
+                .all_neighbors(x, y)
                 .iter()
-                .filter_map(|&(dx, dy)| {
-                    let nx = x.checked_add_signed(dx)?;
-                    let ny = y.checked_add_signed(dy)?;
-
-                    Some((nx, ny))
-                })
-                .for_each(|n| {
-                    let Some(&neighbors_count) = rolls.get(&n) else {
-                        return;
-                    };
-
-                    if neighbors_count - 1 < 4 {
-                        rolls.remove(&n);
-                        queue.push_back(n);
-                    } else {
-                        rolls.entry(n).and_modify(|x| *x -= 1);
-                    }
-                });
-        }
+                .filter(|&&(_, &neighbor)| neighbor == '@')
+                .count();
 
-        Solution::Int(count)
+            if neighbors_count < 4 {
+                queue.push_back((x, y));
+            } else {
+                rolls.insert((x, y), neighbors_count);
+            }
+        }
+    });
+
+    let mut count = 0i64;
+    while !queue.is_empty() {
+        let (x, y) = queue.pop_front().unwrap();
+        count += 1;
+
+        grid.neighbors(x, y, Connectivity::All)
+            .into_iter()
+            .for_each(|(n, _)| {
+                let Some(&neighbors_count) = rolls.get(&n) else {
+                    return;
+                };
+
+                if neighbors_count - 1 < 4 {
+                    rolls.remove(&n);
+                    queue.push_back(n);
+                } else {
+                    rolls.entry(n).and_modify(|x| *x -= 1);
+                }
+            });
     }
+
+    Solution::Int(count)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{Day, Solution, days::day04::Day04};
+    use crate::{Day, Diagnostic, Solution, days::day04::Day04};
 
     const TEST: &str = "..@@.@@@@.
 @@@.@.@.@@
@@ -111,10 +160,24 @@ mod tests {
     #[test]
     fn test_part1() {
         assert_eq!(SOLVER.part1(TEST), Solution::Int(13));
+        assert_eq!(SOLVER.solve(1, "prefix_sum", TEST), Some(Solution::Int(13)));
     }
 
     #[test]
     fn test_part2() {
         assert_eq!(SOLVER.part2(TEST), Solution::Int(43));
+        assert_eq!(SOLVER.solve(2, "queue", TEST), Some(Solution::Int(43)));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(SOLVER.validate(TEST), Some(vec![]));
+        assert_eq!(
+            SOLVER.validate(&TEST[..TEST.len() - 3]),
+            Some(vec![Diagnostic::at(
+                10,
+                "expected 10 columns like the first line, found 7"
+            )])
+        );
     }
 }
@@ -1,6 +1,6 @@
-use std::{cmp::max, ops::RangeInclusive};
+use std::{cmp::max, collections::BTreeSet, fmt::Write, ops::RangeInclusive};
 
-use crate::{Day, Solution};
+use crate::{Day, Solution, Variant, rng::Rng};
 
 pub struct Day05;
 
@@ -27,6 +27,61 @@ impl Day for Day05 {
 
         Solution::Int(expanded_count)
     }
+
+    fn variants(&self) -> Vec<Variant> {
+        vec![Variant {
+            name: "naive",
+            part: 1,
+            solve: part1_naive,
+        }]
+    }
+
+    fn references(&self) -> Vec<Variant> {
+        vec![Variant {
+            name: "naive",
+            part: 2,
+            solve: part2_naive,
+        }]
+    }
+
+    fn generate_input(&self, seed: u64, size: usize) -> Option<String> {
+        let mut rng = Rng::new(seed);
+        let max_value = 10 * size.max(1) as i64;
+        let mut input = String::new();
+
+        for _ in 0..size.max(1) {
+            let start = rng.range(1..=max_value);
+            let end = start + rng.range(0..=size as i64);
+            writeln!(input, "{start}-{end}").unwrap();
+        }
+
+        input.push('\n');
+
+        for _ in 0..size.max(1) {
+            writeln!(input, "{}", rng.range(1..=max_value + size as i64)).unwrap();
+        }
+
+        Some(input)
+    }
+}
+
+fn part1_naive(input: &str) -> Solution {
+    let (ranges, available) = parse_input(input);
+
+    let fresh_count = available
+        .iter()
+        .filter(|i| ranges.iter().any(|r| r.contains(i)))
+        .count();
+
+    Solution::Int(fresh_count as i64)
+}
+
+fn part2_naive(input: &str) -> Solution {
+    let (ranges, _) = parse_input(input);
+
+    let fresh: BTreeSet<i64> = ranges.into_iter().flatten().collect();
+
+    Solution::Int(fresh.len() as i64)
 }
 
 fn parse_input(input: &str) -> (Vec<RangeInclusive<i64>>, Vec<i64>) {
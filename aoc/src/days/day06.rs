@@ -1,4 +1,4 @@
-use crate::{Day, Solution};
+use crate::{Day, Solution, util::grid2d::Grid2D};
 
 pub struct Day06;
 
@@ -93,32 +93,6 @@ fn apply_operations(groups: Vec<Vec<i64>>, operations: Vec<Operation>) -> i64 {
         .sum()
 }
 
-fn transpose(input: &str) -> String {
-    let lines: Vec<&[u8]> = input.lines().map(|line| line.as_bytes()).collect();
-    if lines.is_empty() {
-        return String::new();
-    }
-
-    let rows = lines.len();
-    let cols = lines.iter().map(|line| line.len()).max().unwrap();
-
-    let mut result = Vec::with_capacity(rows * cols + rows);
-
-    for col in 0..cols {
-        for line in &lines {
-            if col < line.len() {
-                result.push(line[col]);
-            } else {
-                result.push(b' ');
-            }
-        }
-        result.push(b'\n');
-    }
-
-    // Safety: all bytes are valid ASCII, so this is safe
-    unsafe { String::from_utf8_unchecked(result) }
-}
-
 fn parse_input2(input: &str) -> (Vec<Vec<i64>>, Vec<Operation>) {
     let (grid_str, ops_str) = input.rsplit_once("\n").unwrap();
 
@@ -127,12 +101,15 @@ fn parse_input2(input: &str) -> (Vec<Vec<i64>>, Vec<Operation>) {
         .map(Operation::parse)
         .collect::<Vec<_>>();
 
-    let transposed = transpose(grid_str);
+    let transposed = Grid2D::from_bytes_padded(grid_str, b' ').transpose();
 
     let mut groups: Vec<Vec<i64>> = Vec::new();
     let mut current: Vec<i64> = Vec::new();
 
-    for line in transposed.lines().map(str::trim) {
+    for line in transposed
+        .rows()
+        .map(|row| str::from_utf8(row).unwrap().trim())
+    {
         if line.is_empty() {
             groups.push(current);
             current = Vec::new();
@@ -166,64 +143,3 @@ mod tests {
         assert_eq!(SOLVER.part2(TEST), Solution::Int(3263827));
     }
 }
-
-// rocket science, i'll let this here so i can remember
-// that i should never make assumptions based on test input
-// fn transpose_digits(numbers: Vec<i64>, op: Operation) -> Vec<i64> {
-//     let mut max_len = 0;
-//     for &n in &numbers {
-//         let len = num_len(n);
-
-//         if len > max_len {
-//             max_len = len;
-//         }
-//     }
-
-//     let mut transposed = vec![0; max_len as usize];
-
-//     match op {
-//         Operation::Mul => {
-//             for digit_pos in 0..max_len {
-//                 let mut new_number = 0;
-//                 for &num in &numbers {
-//                     if num_len(num) <= digit_pos {
-//                         continue;
-//                     }
-
-//                     let digit = (num / 10_i64.pow(digit_pos)) % 10;
-
-//                     new_number = new_number * 10 + digit;
-//                 }
-
-//                 transposed[digit_pos as usize] = new_number;
-//             }
-//         }
-//         Operation::Plus => {
-//             for digit_pos in 0..max_len {
-//                 let mut new_number = 0;
-//                 for &num in &numbers {
-//                     let len = num_len(num);
-//                     if len <= digit_pos {
-//                         continue;
-//                     }
-
-//                     let digit = (num / 10_i64.pow(len - digit_pos - 1)) % 10;
-
-//                     new_number = new_number * 10 + digit;
-//                 }
-
-//                 transposed[digit_pos as usize] = new_number;
-//             }
-//         }
-//     };
-
-//     transposed
-// }
-
-// fn num_len(num: i64) -> u32 {
-//     if num == 0 {
-//         1
-//     } else {
-//         num.checked_ilog10().unwrap() + 1
-//     }
-// }
@@ -1,6 +1,4 @@
-use std::ops::Sub;
-
-use crate::{Day, Solution};
+use crate::{Day, Diagnostic, Solution, util::grid2d::Grid2D, validate};
 
 pub struct Day07;
 
@@ -8,28 +6,28 @@ impl Day for Day07 {
     fn part1(&self, input: &str) -> Solution {
         let (beam_start, char_grid) = parse_input(input.trim());
 
-        let mut grid = vec![vec![false; char_grid[0].len()]; char_grid.len()];
+        let mut beams = Grid2D::new(char_grid.width(), char_grid.height(), false);
 
-        grid[0][beam_start] = true;
+        beams[(beam_start, 0)] = true;
 
         let mut splits = 0;
-        for i in 0..char_grid.len().sub(1) {
+        for (i, row) in char_grid.rows().enumerate().take(char_grid.height() - 1) {
             let next_i = i + 1;
 
-            for (j, &cell) in char_grid[i].iter().enumerate() {
-                if cell == '.' {
-                    if grid[i][j] {
-                        grid[next_i][j] = true;
+            for (j, &cell) in row.iter().enumerate() {
+                if cell == b'.' || cell == b'S' {
+                    if beams[(j, i)] {
+                        beams[(j, next_i)] = true;
                     }
-                } else if cell == '^' && grid[i][j] {
+                } else if cell == b'^' && beams[(j, i)] {
                     splits += 1;
 
                     if j > 0 {
-                        grid[next_i][j - 1] = true;
+                        beams[(j - 1, next_i)] = true;
                     }
 
-                    if j + 1 < char_grid[i].len() {
-                        grid[next_i][j + 1] = true;
+                    if j + 1 < row.len() {
+                        beams[(j + 1, next_i)] = true;
                     }
                 }
             }
@@ -41,51 +39,72 @@ impl Day for Day07 {
     fn part2(&self, input: &str) -> Solution {
         let (beam_start, char_grid) = parse_input(input.trim());
 
-        let mut grid = vec![vec![0; char_grid[0].len()]; char_grid.len()];
+        let mut timelines = Grid2D::new(char_grid.width(), char_grid.height(), 0);
 
-        grid[0][beam_start] = 1;
+        timelines[(beam_start, 0)] = 1;
 
-        for i in 0..char_grid.len().sub(1) {
+        for (i, row) in char_grid.rows().enumerate().take(char_grid.height() - 1) {
             let next_i = i + 1;
 
-            for (j, &cell) in char_grid[i].iter().enumerate() {
-                let current_count = grid[i][j];
+            for (j, &cell) in row.iter().enumerate() {
+                let current_count = timelines[(j, i)];
                 if current_count == 0 {
                     continue;
                 }
 
-                if cell == '.' {
-                    grid[next_i][j] += current_count;
-                } else if cell == '^' {
+                if cell == b'.' || cell == b'S' {
+                    timelines[(j, next_i)] += current_count;
+                } else if cell == b'^' {
                     if j > 0 {
-                        grid[next_i][j - 1] += current_count;
+                        timelines[(j - 1, next_i)] += current_count;
                     }
-                    if j + 1 < char_grid[i].len() {
-                        grid[next_i][j + 1] += current_count;
+                    if j + 1 < row.len() {
+                        timelines[(j + 1, next_i)] += current_count;
                     }
                 }
             }
         }
 
-        let total_timelines: u64 = grid[grid.len() - 1].iter().sum();
+        let total_timelines: u64 = timelines.row(timelines.height() - 1).iter().sum();
         Solution::Int(total_timelines as i64)
     }
+
+    fn validate(&self, input: &str) -> Option<Vec<Diagnostic>> {
+        let mut diagnostics = validate::check_grid(input, ".^S");
+
+        for (i, (n, line)) in validate::numbered_lines(input).into_iter().enumerate() {
+            let starts = line.matches('S').count();
+
+            if i == 0 && starts != 1 {
+                diagnostics.push(Diagnostic::at(
+                    n,
+                    format!("expected exactly one 'S' on the first line, found {starts}"),
+                ));
+            } else if i > 0 && starts > 0 {
+                diagnostics.push(Diagnostic::at(n, "'S' is only allowed on the first line"));
+            }
+        }
+
+        diagnostics.sort_by_key(|d| d.line);
+        Some(diagnostics)
+    }
 }
 
-fn parse_input(input: &str) -> (usize, Vec<Vec<char>>) {
-    let mut lines = input.lines();
-    let first_line = lines.next().unwrap();
-    let rest = lines.into_iter();
+/// Grid including the first line, and the column of its `S`.
+fn parse_input(input: &str) -> (usize, Grid2D<u8>) {
+    let grid = Grid2D::from_bytes(input).unwrap_or_else(|e| panic!("{e}"));
+    let beam_start = grid
+        .rows()
+        .next()
+        .and_then(|row| row.iter().position(|&cell| cell == b'S'))
+        .expect("No 'S' on the first line");
 
-    (
-        first_line.find('S').unwrap(),
-        rest.map(|x| x.chars().collect()).collect(),
-    )
+    (beam_start, grid)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{Day, Solution, days::day07::Day07};
+    use crate::{Day, Diagnostic, Solution, days::day07::Day07};
 
     const TEST: &str = ".......S.......
 ...............
@@ -115,4 +134,23 @@ mod tests {
     fn test_part2() {
         assert_eq!(SOLVER.part2(TEST), Solution::Int(40));
     }
+
+    #[test]
+    fn test_single_line() {
+        assert_eq!(SOLVER.part1("..S.."), Solution::Int(0));
+        assert_eq!(SOLVER.part2("..S.."), Solution::Int(1));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(SOLVER.validate(TEST), Some(vec![]));
+        assert_eq!(
+            SOLVER.validate("...\n.S.\n.^"),
+            Some(vec![
+                Diagnostic::at(1, "expected exactly one 'S' on the first line, found 0"),
+                Diagnostic::at(2, "'S' is only allowed on the first line"),
+                Diagnostic::at(3, "expected 3 columns like the first line, found 2"),
+            ])
+        );
+    }
 }
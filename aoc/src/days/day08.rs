@@ -1,6 +1,9 @@
-use rustc_hash::FxHashMap;
+use std::ops::ControlFlow;
 
-use crate::{Day, Solution, dsu::Dsu};
+use crate::{
+    Day, Solution, Variant,
+    util::{dsu::Dsu, mst},
+};
 
 pub struct Day08;
 
@@ -9,14 +12,7 @@ impl Day for Day08 {
         let points = parse_input(input);
         let n = points.len();
 
-        let mut edges = Vec::with_capacity(n * (n - 1) / 2);
-        for i in 0..n {
-            for j in (i + 1)..n {
-                let dist = euclidian_distance(points[i], points[j]);
-                edges.push((dist, i, j));
-            }
-        }
-
+        let mut edges = get_edges(&points);
         edges.sort_unstable_by_key(|e| e.0);
 
         let connection_limit = if n == 20 { 10 } else { 1000 };
@@ -31,42 +27,52 @@ impl Day for Day08 {
             dsu.union(u, v);
         }
 
-        let mut component_sizes: FxHashMap<usize, i64> = FxHashMap::default();
-        for i in 0..n {
-            let root = dsu.find(i);
-            *component_sizes.entry(root).or_default() += 1;
-        }
-
-        let mut sizes: Vec<i64> = component_sizes.values().cloned().collect();
-        sizes.sort_unstable_by(|a, b| b.cmp(a));
-
-        let result: i64 = sizes.iter().take(3).product();
+        let result: i64 = dsu
+            .largest_components(3)
+            .iter()
+            .map(|&size| size as i64)
+            .product();
 
         Solution::Int(result)
     }
 
     fn part2(&self, input: &str) -> Solution {
         let points = parse_input(input);
-        let n = points.len();
-
-        let edges = get_sorted_edges(&points);
-
-        let mut dsu = Dsu::new(n);
-        let mut components = n;
 
-        for (_, u, v) in edges {
-            if dsu.find(u) != dsu.find(v) {
-                dsu.union(u, v);
-                components -= 1;
+        let tree = mst::kruskal(points.len(), get_edges(&points), |_, _| {
+            ControlFlow::Continue(())
+        });
 
-                if components == 1 {
-                    let ans = points[u].0 * points[v].0;
-                    return Solution::Int(ans);
-                }
-            }
+        match tree.last() {
+            Some(&(_, u, v)) => Solution::Int(points[u].0 * points[v].0),
+            None => Solution::Int(0),
         }
+    }
 
-        Solution::Int(0)
+    fn variants(&self) -> Vec<Variant> {
+        vec![Variant {
+            name: "prim",
+            part: 2,
+            solve: part2_prim,
+        }]
+    }
+}
+
+/// The edge connecting the last two components is the longest one of the
+/// minimum spanning tree. Equal distances are ordered by their nodes like
+/// Kruskal does, so both pick the same edge.
+fn part2_prim(input: &str) -> Solution {
+    let points = parse_input(input);
+
+    let tree = mst::prim(
+        points.len(),
+        |i, j| (euclidian_distance(points[i], points[j]), i.min(j), i.max(j)),
+        |_| ControlFlow::Continue(()),
+    );
+
+    match tree.iter().max_by_key(|edge| edge.0) {
+        Some(&(_, u, v)) => Solution::Int(points[u].0 * points[v].0),
+        None => Solution::Int(0),
     }
 }
 
@@ -94,7 +100,7 @@ fn parse_input(input: &str) -> Vec<Coord> {
         .collect()
 }
 
-fn get_sorted_edges(points: &[Coord]) -> Vec<(i64, usize, usize)> {
+fn get_edges(points: &[Coord]) -> Vec<(i64, usize, usize)> {
     let n = points.len();
     let mut edges = Vec::with_capacity(n * (n - 1) / 2);
 
@@ -104,7 +110,6 @@ fn get_sorted_edges(points: &[Coord]) -> Vec<(i64, usize, usize)> {
             edges.push((dist, i, j));
         }
     }
-    edges.sort_unstable_by(|a, b| a.0.cmp(&b.0));
     edges
 }
 
@@ -154,5 +159,13 @@ mod tests {
     #[test]
     fn test_part2() {
         assert_eq!(SOLVER.part2(TEST), Solution::Int(25272));
+        assert_eq!(SOLVER.solve(2, "prim", TEST), Some(Solution::Int(25272)));
+    }
+
+    #[test]
+    fn test_part2_ties() {
+        let square = "10,0,0\n11,0,0\n10,1,0\n11,1,0\n12,0,0\n12,1,0";
+        assert_eq!(SOLVER.part2(square), Solution::Int(132));
+        assert_eq!(SOLVER.solve(2, "prim", square), Some(Solution::Int(132)));
     }
 }
@@ -1,6 +1,8 @@
+use std::{collections::VecDeque, fmt::Write};
+
 use itertools::Itertools;
 
-use crate::{Day, Solution};
+use crate::{Day, Diagnostic, Solution, Variant, rng::Rng, validate};
 
 pub struct Day09;
 
@@ -33,6 +35,11 @@ impl Day for Day09 {
         let tiles = parse_input(input);
         let edges = build_polygon(&tiles);
 
+        // Same polygon with doubled coordinates, so the center of any
+        // rectangle between two tiles is a lattice point
+        let doubled: Vec<(i64, i64)> = tiles.iter().map(|&(x, y)| (2 * x, 2 * y)).collect();
+        let doubled_edges = build_polygon(&doubled);
+
         let combinations = tiles.iter().combinations(2);
 
         let mut max_area = 0;
@@ -45,17 +52,17 @@ impl Day for Day09 {
             let y_min = a.1.min(b.1);
             let y_max = a.1.max(b.1);
 
-            let corner1 = (x_min, y_max);
-            let corner2 = (x_max, y_min);
-
-            if !point_in_polygon(corner1, &edges) {
-                continue;
-            }
-            if !point_in_polygon(corner2, &edges) {
-                continue;
-            }
-
-            if rectangle_has_interior_intersection((x_min, x_max, y_min, y_max), &edges) {
+            let rect = (x_min, x_max, y_min, y_max);
+            let inside = if x_min == x_max || y_min == y_max {
+                line_in_polygon(rect, &edges, &doubled_edges)
+            } else {
+                // Nothing crosses the rectangle, so it is either fully inside
+                // or fully outside: its center tells which
+                let center = (x_min + x_max, y_min + y_max);
+                !rectangle_has_interior_intersection(rect, &edges)
+                    && point_in_polygon(center, &doubled_edges)
+            };
+            if !inside {
                 continue;
             }
 
@@ -67,6 +74,170 @@ impl Day for Day09 {
 
         Solution::Int(max_area)
     }
+
+    fn references(&self) -> Vec<Variant> {
+        vec![Variant {
+            name: "naive",
+            part: 2,
+            solve: part2_naive,
+        }]
+    }
+
+    fn validate(&self, input: &str) -> Option<Vec<Diagnostic>> {
+        let mut diagnostics = Vec::new();
+        let mut points = Vec::new();
+
+        for (n, line) in validate::numbered_lines(input) {
+            let coords = line
+                .split_once(',')
+                .and_then(|(x, y)| Some((x.parse::<i64>().ok()?, y.parse::<i64>().ok()?)));
+
+            match coords {
+                Some(point) => points.push((n, point)),
+                None => {
+                    diagnostics.push(Diagnostic::at(n, format!("expected `x,y`, found {line:?}")))
+                }
+            }
+        }
+
+        if points.len() < 4 {
+            diagnostics.push(Diagnostic::global(format!(
+                "a polygon needs at least 4 points, found {}",
+                points.len()
+            )));
+            return Some(diagnostics);
+        }
+
+        // Each point must share a row or a column with the previous one
+        for i in 0..points.len() {
+            let (_, prev) = points[(i + points.len() - 1) % points.len()];
+            let (n, point) = points[i];
+
+            if point == prev {
+                diagnostics.push(Diagnostic::at(n, "same point as the previous one"));
+            } else if point.0 != prev.0 && point.1 != prev.1 {
+                diagnostics.push(Diagnostic::at(
+                    n,
+                    format!(
+                        "{},{} is not axis-aligned with the previous point {},{}",
+                        point.0, point.1, prev.0, prev.1
+                    ),
+                ));
+            }
+        }
+
+        Some(diagnostics)
+    }
+
+    fn generate_input(&self, seed: u64, size: usize) -> Option<String> {
+        let mut rng = Rng::new(seed);
+
+        // An x-monotone polygon: `size` columns, each spanning [low, high],
+        // where neighbouring columns overlap. Every coordinate step is at
+        // least 2 so there's always a tile between two parallel edges.
+        let columns = size.max(1);
+        let mut xs = vec![rng.range(0..=5)];
+        let mut spans = vec![{
+            let high = rng.range(20..=40);
+            (high - rng.range(2..=10), high)
+        }];
+
+        for _ in 0..columns {
+            xs.push(xs.last().unwrap() + rng.range(2..=6));
+        }
+
+        while spans.len() < columns {
+            let (low, high) = *spans.last().unwrap();
+            let next_low = low + rng.range(-6..=6);
+            let next_high = high + rng.range(-6..=6);
+
+            if next_low >= 0
+                && (next_low - low).abs() >= 2
+                && (next_high - high).abs() >= 2
+                && low.max(next_low) + 2 <= high.min(next_high)
+            {
+                spans.push((next_low, next_high));
+            }
+        }
+
+        let mut points = vec![(xs[0], spans[0].0)];
+        for (i, &(_, high)) in spans.iter().enumerate() {
+            points.push((xs[i], high));
+            points.push((xs[i + 1], high));
+        }
+        for (i, &(low, _)) in spans.iter().enumerate().rev() {
+            points.push((xs[i + 1], low));
+            if i > 0 {
+                points.push((xs[i], low));
+            }
+        }
+
+        if rng.bool() {
+            points.iter_mut().for_each(|p| *p = (p.1, p.0));
+        }
+        if rng.bool() {
+            points.reverse();
+        }
+        let shift = rng.index(points.len());
+        points.rotate_left(shift);
+
+        let mut input = String::new();
+        for (x, y) in points {
+            writeln!(input, "{x},{y}").unwrap();
+        }
+
+        Some(input)
+    }
+}
+
+/// Rasterizes the polygon, flood fills the outside and checks every tile of
+/// every candidate rectangle.
+fn part2_naive(input: &str) -> Solution {
+    let tiles = parse_input(input);
+
+    let width = tiles.iter().map(|t| t.0).max().unwrap() as usize + 3;
+    let height = tiles.iter().map(|t| t.1).max().unwrap() as usize + 3;
+
+    // Shifted by one so the border of the grid is always outside.
+    let mut boundary = vec![vec![false; width]; height];
+    for i in 0..tiles.len() {
+        let (a, b) = (tiles[i], tiles[(i + 1) % tiles.len()]);
+        for x in a.0.min(b.0)..=a.0.max(b.0) {
+            for y in a.1.min(b.1)..=a.1.max(b.1) {
+                boundary[y as usize + 1][x as usize + 1] = true;
+            }
+        }
+    }
+
+    let mut outside = vec![vec![false; width]; height];
+    let mut queue = VecDeque::from([(0usize, 0usize)]);
+    outside[0][0] = true;
+
+    while let Some((x, y)) = queue.pop_front() {
+        for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+            let (Some(nx), Some(ny)) = (x.checked_add_signed(dx), y.checked_add_signed(dy)) else {
+                continue;
+            };
+            if nx < width && ny < height && !boundary[ny][nx] && !outside[ny][nx] {
+                outside[ny][nx] = true;
+                queue.push_back((nx, ny));
+            }
+        }
+    }
+
+    let max_area = tiles
+        .iter()
+        .tuple_combinations()
+        .filter(|&(a, b)| {
+            (a.0.min(b.0)..=a.0.max(b.0)).all(|x| {
+                (a.1.min(b.1)..=a.1.max(b.1)).all(|y| !outside[y as usize + 1][x as usize + 1])
+            })
+        })
+        .map(|(a, b)| ((a.0 - b.0).abs() + 1) * ((a.1 - b.1).abs() + 1))
+        .max()
+        .unwrap_or(0);
+
+    Solution::Int(max_area)
 }
 
 fn parse_input(input: &str) -> Vec<(i64, i64)> {
@@ -133,19 +304,56 @@ fn point_in_polygon(point: (i64, i64), edges: &[Edge]) -> bool {
         }
     }
 
-    // Ray casting algorithm
+    // Ray casting algorithm, edges are half-open so a ray going through a
+    // vertex is only counted once
     let mut count = 0;
     for edge in edges {
-        if let Edge::Vertical { x, y1, y2 } = edge {
-            if *x > point.0 && point.1 > *y1 && point.1 < *y2 {
-                count += 1;
-            }
+        if let Edge::Vertical { x, y1, y2 } = edge
+            && *x > point.0
+            && point.1 >= *y1
+            && point.1 < *y2
+        {
+            count += 1;
         }
     }
 
     count % 2 == 1
 }
 
+/// Checks a rectangle one tile thick, which has no interior for an edge to
+/// cross. The line is cut wherever a perpendicular edge reaches it, and each
+/// piece is then either fully inside or fully outside, like a rectangle.
+fn line_in_polygon(
+    rect: (i64, i64, i64, i64), // (x_min, x_max, y_min, y_max)
+    edges: &[Edge],
+    doubled_edges: &[Edge],
+) -> bool {
+    let (x_min, x_max, y_min, y_max) = rect;
+
+    let mut cuts = vec![(x_min, y_min), (x_max, y_max)];
+    for edge in edges {
+        match *edge {
+            Edge::Vertical { x, y1, y2 }
+                if y_min == y_max && x > x_min && x < x_max && (y1..=y2).contains(&y_min) =>
+            {
+                cuts.push((x, y_min));
+            }
+            Edge::Horizontal { y, x1, x2 }
+                if x_min == x_max && y > y_min && y < y_max && (x1..=x2).contains(&x_min) =>
+            {
+                cuts.push((x_min, y));
+            }
+            _ => {}
+        }
+    }
+    cuts.sort_unstable();
+    cuts.dedup();
+
+    // Middle of each piece, in doubled coordinates
+    cuts.windows(2)
+        .all(|w| point_in_polygon((w[0].0 + w[1].0, w[0].1 + w[1].1), doubled_edges))
+}
+
 fn rectangle_has_interior_intersection(
     rect: (i64, i64, i64, i64), // (x_min, x_max, y_min, y_max)
     edges: &[Edge],
@@ -180,7 +388,7 @@ fn rectangle_has_interior_intersection(
 
 #[cfg(test)]
 mod tests {
-    use crate::{Day, Solution, days::day09::Day09};
+    use crate::{Day, Diagnostic, Solution, days::day09::Day09};
 
     const TEST: &str = "\
 7,1
@@ -193,6 +401,24 @@ mod tests {
 7,3\
 ";
 
+    const COMB: &str = "\
+0,0
+2,0
+2,2
+4,2
+4,0
+10,0
+10,2
+12,2
+12,0
+14,0
+14,3
+8,3
+8,1
+6,1
+6,3
+0,3";
+
     const SOLVER: Day09 = Day09;
 
     #[test]
@@ -204,4 +430,24 @@ mod tests {
     fn test_part2() {
         assert_eq!(SOLVER.part2(TEST), Solution::Int(24));
     }
+
+    #[test]
+    fn test_part2_thin_rectangle() {
+        // The bottom row from 0,0 to 14,0 crosses two notches, though its
+        // middle tile is inside
+        assert_eq!(SOLVER.part2(COMB), Solution::Int(12));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(SOLVER.validate(TEST), Some(vec![]));
+        assert_eq!(
+            SOLVER.validate("7,1\n11,1\n11;7\n9,7\n7,3"),
+            Some(vec![
+                Diagnostic::at(3, "expected `x,y`, found \"11;7\""),
+                Diagnostic::at(4, "9,7 is not axis-aligned with the previous point 11,1"),
+                Diagnostic::at(5, "7,3 is not axis-aligned with the previous point 9,7"),
+            ])
+        );
+    }
 }
@@ -1,6 +1,6 @@
-use std::collections::VecDeque;
+use std::{collections::VecDeque, fmt::Write};
 
-use crate::{Day, Solution};
+use crate::{Day, Solution, Variant, rng::Rng};
 use good_lp::{Expression, Solution as _, SolverModel, default_solver, variable, variables};
 
 pub struct Day10;
@@ -32,6 +32,135 @@ impl Day for Day10 {
 
         Solution::Int(total_presses)
     }
+
+    fn variants(&self) -> Vec<Variant> {
+        vec![Variant {
+            name: "gf2",
+            part: 1,
+            solve: part1_gf2,
+        }]
+    }
+
+    fn generate_input(&self, seed: u64, size: usize) -> Option<String> {
+        let mut rng = Rng::new(seed);
+        let mut input = String::new();
+
+        for _ in 0..size.max(1) {
+            let lights = rng.range(3..=10) as usize;
+            let buttons: Vec<Vec<usize>> = (0..rng.range(2..=10))
+                .map(|_| {
+                    let mut wired: Vec<usize> = (0..lights).collect();
+                    rng.shuffle(&mut wired);
+                    wired.truncate(1 + rng.index(lights));
+                    wired.sort_unstable();
+                    wired
+                })
+                .collect();
+
+            // Derive the targets from actual presses so the machine is solvable.
+            let mut target = vec![false; lights];
+            let mut joltages = vec![0; lights];
+            for button in &buttons {
+                let presses = rng.range(0..=5);
+                for &light in button {
+                    target[light] ^= presses % 2 == 1;
+                    joltages[light] += presses;
+                }
+            }
+
+            let target: String = target
+                .iter()
+                .map(|&on| if on { '#' } else { '.' })
+                .collect();
+            write!(input, "[{target}]").unwrap();
+            for button in &buttons {
+                let wired: Vec<String> = button.iter().map(usize::to_string).collect();
+                write!(input, " ({})", wired.join(",")).unwrap();
+            }
+            let joltages: Vec<String> = joltages.iter().map(i64::to_string).collect();
+            writeln!(input, " {{{}}}", joltages.join(",")).unwrap();
+        }
+
+        Some(input)
+    }
+}
+
+fn part1_gf2(input: &str) -> Solution {
+    let mut total_presses = 0;
+
+    for line in input.lines() {
+        let machine = Machine::from(line);
+
+        let min_presses = gf2_min_presses(machine.lights, machine.target_lights, &machine.buttons);
+        total_presses += min_presses as i64;
+    }
+
+    Solution::Int(total_presses)
+}
+
+/// Pressing a button twice cancels out, so part 1 is the linear system
+/// `buttons * x = start ^ target` over GF(2). Gaussian elimination gives one
+/// solution, then every combination of the null space is tried to find the
+/// one with the fewest presses. The buttons each solution uses are tracked in
+/// a `u64`, so there can be at most 64 of them.
+fn gf2_min_presses(start: u16, target: u16, buttons: &[u16]) -> u32 {
+    assert!(
+        buttons.len() <= 64,
+        "gf2 handles at most 64 buttons per machine, found {}",
+        buttons.len()
+    );
+
+    // basis[bit] holds a reduced vector whose highest set bit is `bit`,
+    // along with the set of buttons that produce it.
+    let mut basis: [Option<(u16, u64)>; 16] = [None; 16];
+    let mut null_space = Vec::new();
+
+    for (i, &button) in buttons.iter().enumerate() {
+        let mut vector = button;
+        let mut presses = 1u64 << i;
+
+        while vector != 0 {
+            let bit = 15 - vector.leading_zeros() as usize;
+            match basis[bit] {
+                Some((v, p)) => {
+                    vector ^= v;
+                    presses ^= p;
+                }
+                None => {
+                    basis[bit] = Some((vector, presses));
+                    break;
+                }
+            }
+        }
+
+        if vector == 0 {
+            null_space.push(presses);
+        }
+    }
+
+    let mut remaining = start ^ target;
+    let mut solution = 0u64;
+
+    while remaining != 0 {
+        let bit = 15 - remaining.leading_zeros() as usize;
+        let Some((v, p)) = basis[bit] else {
+            return u32::MAX;
+        };
+        remaining ^= v;
+        solution ^= p;
+    }
+
+    (0..1u64 << null_space.len())
+        .map(|subset| {
+            null_space
+                .iter()
+                .enumerate()
+                .filter(|&(i, _)| (subset >> i) & 1 == 1)
+                .fold(solution, |acc, (_, &p)| acc ^ p)
+                .count_ones()
+        })
+        .min()
+        .unwrap()
 }
 
 fn solve_machine_lp(machine: &Machine) -> i64 {
@@ -207,7 +336,10 @@ impl From<&str> for Machine {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Day, Solution, days::day10::Day10};
+    use crate::{
+        Day, Solution,
+        days::day10::{Day10, gf2_min_presses, part1_gf2},
+    };
 
     const TEST: &str = "\
     [.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
@@ -222,6 +354,17 @@ mod tests {
         assert_eq!(SOLVER.part1(TEST), Solution::Int(7));
     }
 
+    #[test]
+    fn test_part1_gf2() {
+        assert_eq!(part1_gf2(TEST), Solution::Int(7));
+    }
+
+    #[test]
+    #[should_panic(expected = "gf2 handles at most 64 buttons per machine, found 65")]
+    fn test_gf2_too_many_buttons() {
+        gf2_min_presses(0, 1, &[1; 65]);
+    }
+
     #[test]
     fn test_part2() {
         assert_eq!(SOLVER.part2(TEST), Solution::Int(33))
@@ -1,129 +1,248 @@
+use std::fmt::Write;
+
+use itertools::Itertools;
 use rustc_hash::FxHashMap;
 
-use crate::{Day, Solution};
+use crate::{
+    Day, Diagnostic, Solution, Variant,
+    rng::Rng,
+    util::{
+        graph::{Graph, PathCount},
+        memo::Memo,
+    },
+    validate,
+};
 
 pub struct Day11;
 
 impl Day for Day11 {
     fn part1(&self, input: &str) -> Solution {
-        let start = "you";
-        let end = "out";
-
-        let graph = Graph::from(input.trim());
+        let graph: Graph = input.trim().parse().unwrap();
 
-        Solution::Int(graph.n_path_from_to(start, end))
+        Solution::Int(n_path(&graph, "you", "out", &[]))
     }
 
     fn part2(&self, input: &str) -> Solution {
-        let start = "svr";
-        let end = "out";
-
-        let graph = Graph::from(input.trim());
+        let graph: Graph = input.trim().parse().unwrap();
 
-        Solution::Int(graph.n_path_from_to2(start, end))
+        Solution::Int(n_path(&graph, "svr", "out", &["dac", "fft"]))
     }
-}
-
-#[derive(Debug)]
-struct Graph<'a> {
-    adjency: FxHashMap<&'a str, Vec<&'a str>>,
-}
-
-impl<'a> From<&'a str> for Graph<'a> {
-    fn from(value: &'a str) -> Self {
-        let adjency = value
-            .lines()
-            .map(|line| {
-                let (node_name, rest) = line.split_once(':').unwrap();
-
-                (node_name, rest.split_whitespace().collect())
-            })
-            .collect();
 
-        Graph { adjency }
+    fn references(&self) -> Vec<Variant> {
+        vec![
+            Variant {
+                name: "naive",
+                part: 1,
+                solve: |input| {
+                    let graph = input.trim().parse().unwrap();
+                    Solution::Int(n_path_naive(&graph, "you", "out", &[]))
+                },
+            },
+            Variant {
+                name: "naive",
+                part: 2,
+                solve: |input| {
+                    let graph = input.trim().parse().unwrap();
+                    Solution::Int(n_path_naive(&graph, "svr", "out", &["dac", "fft"]))
+                },
+            },
+        ]
     }
-}
-
-impl<'a> Graph<'a> {
-    pub fn n_path_from_to(&self, node: &str, to: &str) -> i64 {
-        let mut memo = FxHashMap::default();
 
-        return self.n_path_from_to_helper(node, to, &mut memo);
+    fn variants(&self) -> Vec<Variant> {
+        vec![
+            Variant {
+                name: "memo",
+                part: 1,
+                solve: |input| {
+                    let graph = input.trim().parse().unwrap();
+                    Solution::Int(n_path_memo(&graph, "you", "out", &[]))
+                },
+            },
+            Variant {
+                name: "memo",
+                part: 2,
+                solve: |input| {
+                    let graph = input.trim().parse().unwrap();
+                    Solution::Int(n_path_memo(&graph, "svr", "out", &["dac", "fft"]))
+                },
+            },
+        ]
     }
 
-    fn n_path_from_to_helper(
-        &'a self,
-        node: &'a str,
-        to: &'a str,
-        memo: &mut FxHashMap<&'a str, i64>,
-    ) -> i64 {
-        if node == to {
-            return 1;
+    fn validate(&self, input: &str) -> Option<Vec<Diagnostic>> {
+        let mut diagnostics = Vec::new();
+        let mut defined = FxHashMap::default();
+        let mut references = Vec::new();
+
+        for (n, line) in validate::numbered_lines(input) {
+            let Some((name, rest)) = line.split_once(':') else {
+                diagnostics.push(Diagnostic::at(n, "expected `name: child child ...`"));
+                continue;
+            };
+
+            if let Some(first) = defined.insert(name, n) {
+                diagnostics.push(Diagnostic::at(
+                    n,
+                    format!("{name} is already defined on line {first}"),
+                ));
+            }
+            references.extend(rest.split_whitespace().map(|child| (n, child)));
         }
 
-        if let Some(&val) = memo.get(node) {
-            return val;
+        // "out" is the only node allowed to have no outgoing edges
+        for (n, child) in references {
+            if child != "out" && !defined.contains_key(child) {
+                diagnostics.push(Diagnostic::at(n, format!("{child} is never defined")));
+            }
         }
 
-        let childs = self.adjency.get(node).unwrap();
-        let mut total = 0;
-
-        for child in childs {
-            total += self.n_path_from_to_helper(child, to, memo);
+        diagnostics.sort_by_key(|d| d.line);
+
+        // The solvers count paths, which are only finite without cycles
+        if diagnostics.is_empty()
+            && let Ok(graph) = input.trim().parse::<Graph>()
+            && let Some(cycle) = graph.find_cycle()
+        {
+            let names: Vec<&str> = cycle.iter().map(|&u| graph.name(u)).collect();
+            diagnostics.push(Diagnostic::global(format!(
+                "devices form a cycle: {} -> {}",
+                names.join(" -> "),
+                names[0]
+            )));
         }
 
-        memo.insert(node, total);
+        Some(diagnostics)
+    }
+
+    fn generate_input(&self, seed: u64, size: usize) -> Option<String> {
+        let mut rng = Rng::new(seed);
+
+        // A random topological order: every node links to 1 to 3 nodes after
+        // it, so every node reaches "out" and there are no cycles.
+        let mut names: Vec<String> = Vec::new();
+        while names.len() < size {
+            let name: String = (0..3)
+                .map(|_| (b'a' + rng.index(26) as u8) as char)
+                .collect();
+            if !["svr", "you", "dac", "fft", "out"].contains(&name.as_str())
+                && !names.contains(&name)
+            {
+                names.push(name);
+            }
+        }
+        for special in ["dac", "fft"] {
+            let at = rng.index(names.len() + 1);
+            names.insert(at, special.to_string());
+        }
+        let mut first = ["svr", "you"].map(String::from);
+        rng.shuffle(&mut first);
+        names.splice(0..0, first);
+        names.push("out".to_string());
+
+        let mut input = String::new();
+        for (i, name) in names.iter().enumerate().take(names.len() - 1) {
+            let mut later: Vec<usize> = (i + 1..names.len()).collect();
+            rng.shuffle(&mut later);
+
+            let children = later
+                .iter()
+                .take(1 + rng.index(3))
+                .map(|&j| names[j].as_str())
+                .join(" ");
+            writeln!(input, "{name}: {children}").unwrap();
+        }
 
-        total
+        Some(input)
     }
+}
+
+/// Ids of `from`, `to` and then `required`, `None` if one of them is
+/// missing from the graph.
+fn node_ids(graph: &Graph, from: &str, to: &str, required: &[&str]) -> Option<Vec<usize>> {
+    [from, to]
+        .iter()
+        .chain(required)
+        .map(|name| graph.id(name))
+        .collect()
+}
 
-    // PART 2 \\
+/// Number of paths from `from` to `to` going through every node of
+/// `required`, 0 when one of them is missing from the graph.
+fn n_path(graph: &Graph, from: &str, to: &str, required: &[&str]) -> i64 {
+    let Some(ids) = node_ids(graph, from, to, required) else {
+        return 0;
+    };
 
-    pub fn n_path_from_to2(&self, node: &str, to: &str) -> i64 {
-        let mut memo = FxHashMap::default();
+    match graph.count_paths_through(ids[0], ids[1], &ids[2..]) {
+        PathCount::Finite(paths) => i64::try_from(paths).expect("Path count overflows i64"),
+        PathCount::Infinite => panic!("infinitely many paths, the devices have a cycle"),
+    }
+}
 
-        return self.n_path_from_to_helper2(node, to, &mut memo, false, false);
+/// Enumerates every path one by one, counting those that go through all
+/// of `required`.
+fn n_path_naive(graph: &Graph, from: &str, to: &str, required: &[&str]) -> i64 {
+    // Walking around a cycle would overflow the stack instead of ending
+    if let Some(cycle) = graph.find_cycle() {
+        let names: Vec<&str> = cycle.iter().map(|&u| graph.name(u)).collect();
+        panic!(
+            "infinitely many paths, the devices have a cycle: {} -> {}",
+            names.join(" -> "),
+            names[0]
+        );
     }
 
-    fn n_path_from_to_helper2(
-        &'a self,
-        node: &'a str,
-        to: &'a str,
-        memo: &mut FxHashMap<(&'a str, bool, bool), i64>,
-        dac: bool,
-        fft: bool,
-    ) -> i64 {
+    fn walk(graph: &Graph, node: usize, to: usize, required: &[usize]) -> i64 {
+        let required: Vec<usize> = required.iter().copied().filter(|&r| r != node).collect();
+
         if node == to {
-            if dac && fft {
-                return 1;
-            } else {
-                return 0;
-            }
+            return required.is_empty() as i64;
         }
 
-        if let Some(&val) = memo.get(&(node, dac, fft)) {
-            return val;
-        }
+        graph
+            .neighbors(node)
+            .iter()
+            .map(|&child| walk(graph, child, to, &required))
+            .sum()
+    }
 
-        let dac = dac || node == "dac";
-        let fft = fft || node == "fft";
+    match node_ids(graph, from, to, required) {
+        Some(ids) => walk(graph, ids[0], ids[1], &ids[2..]),
+        None => 0,
+    }
+}
 
-        let childs = self.adjency.get(node).unwrap();
-        let mut total = 0;
+/// Memoized count of the paths from every `(node, required nodes seen so
+/// far)` state.
+fn n_path_memo(graph: &Graph, from: &str, to: &str, required: &[&str]) -> i64 {
+    let Some(ids) = node_ids(graph, from, to, required) else {
+        return 0;
+    };
+    let (to, required) = (ids[1], &ids[2..]);
+    let all_seen = (1u32 << required.len()) - 1;
+
+    let mut paths = Memo::new(|rec, (node, seen): (usize, u32)| {
+        let seen = required
+            .iter()
+            .enumerate()
+            .filter(|&(_, &r)| r == node)
+            .fold(seen, |seen, (i, _)| seen | 1 << i);
 
-        for child in childs {
-            total += self.n_path_from_to_helper2(child, to, memo, dac, fft);
+        if node == to {
+            return Some((seen == all_seen) as u64);
         }
 
-        memo.insert((node, dac, fft), total);
+        let counts = rec.get_all(graph.neighbors(node).iter().map(|&child| (child, seen)))?;
+        Some(counts.iter().sum())
+    });
 
-        total
-    }
+    i64::try_from(paths.get((ids[0], 0))).expect("Path count overflows i64")
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{Day, Solution, days::day11::Day11};
+    use crate::{Day, Diagnostic, Solution, days::day11::Day11};
 
     const TEST: &str = "\
 aaa: you hhh
@@ -160,11 +279,42 @@ hhh: out\
     fn test_part1() {
         let result = SOLVER.part1(TEST);
         assert_eq!(result, Solution::Int(5));
+        assert_eq!(SOLVER.solve(1, "memo", TEST), Some(Solution::Int(5)));
+        assert_eq!(SOLVER.solve(1, "naive", TEST), Some(Solution::Int(5)));
+        assert_eq!(SOLVER.implementations(1), ["default", "memo"]);
     }
 
     #[test]
     fn test_part2() {
         let result = SOLVER.part2(TEST2);
         assert_eq!(result, Solution::Int(2));
+        assert_eq!(SOLVER.solve(2, "memo", TEST2), Some(Solution::Int(2)));
+    }
+
+    #[test]
+    #[should_panic(expected = "the devices have a cycle: aaa -> bbb -> aaa")]
+    fn test_naive_cycle() {
+        SOLVER.solve(1, "naive", "you: aaa\naaa: bbb\nbbb: aaa out");
+    }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(SOLVER.validate(TEST), Some(vec![]));
+        assert_eq!(SOLVER.validate(TEST2), Some(vec![]));
+        assert_eq!(
+            SOLVER.validate("aaa: bbb out\nbbb ccc\naaa: ccc"),
+            Some(vec![
+                Diagnostic::at(1, "bbb is never defined"),
+                Diagnostic::at(2, "expected `name: child child ...`"),
+                Diagnostic::at(3, "aaa is already defined on line 1"),
+                Diagnostic::at(3, "ccc is never defined"),
+            ])
+        );
+        assert_eq!(
+            SOLVER.validate("aaa: bbb\nbbb: ccc out\nccc: aaa"),
+            Some(vec![Diagnostic::global(
+                "devices form a cycle: aaa -> bbb -> ccc -> aaa"
+            )])
+        );
     }
 }
@@ -1,8 +1,12 @@
-use std::collections::VecDeque;
+use std::convert::Infallible;
 
-use rustc_hash::FxHashSet;
-
-use crate::{Day, Solution};
+use crate::{
+    Day, Solution,
+    util::{
+        bitgrid::BitGrid,
+        grid2d::{Connectivity, Grid2D},
+    },
+};
 
 pub struct Day12;
 
@@ -13,12 +17,7 @@ impl Day for Day12 {
 
         let shape_areas: std::collections::HashMap<usize, usize> = variations
             .iter()
-            .map(|(&id, vars)| {
-                (
-                    id,
-                    vars[0].rows.iter().map(|r| r.count_ones() as usize).sum(),
-                )
-            })
+            .map(|(&id, vars)| (id, vars[0].count_ones()))
             .collect();
 
         let mut valid_count = 0;
@@ -47,7 +46,7 @@ impl Day for Day12 {
             }
 
             let min_area = items.last().unwrap().area;
-            let mut grid = vec![0u128; height];
+            let mut grid = BitGrid::new(width, height);
 
             if solve_recursive(
                 &mut grid,
@@ -70,13 +69,6 @@ impl Day for Day12 {
     }
 }
 
-#[derive(Clone, Debug)]
-struct Variation {
-    h: usize,
-    w: usize,
-    rows: Vec<u128>,
-}
-
 #[derive(Clone, Debug)]
 struct Item {
     id: usize,
@@ -85,13 +77,11 @@ struct Item {
     placed_c: usize,
 }
 
-fn parse_input(
-    input: &str,
-) -> (
-    std::collections::HashMap<usize, Vec<(u128, usize)>>,
-    Vec<(usize, usize, Vec<usize>)>,
-) {
-    let mut shapes = std::collections::HashMap::new();
+type Shapes = std::collections::HashMap<usize, Grid2D<bool>>;
+type Queries = Vec<(usize, usize, Vec<usize>)>;
+
+fn parse_input(input: &str) -> (Shapes, Queries) {
+    let mut shape_lines = std::collections::HashMap::new();
     let mut grids = Vec::new();
     let mut current_id = 0;
 
@@ -101,9 +91,7 @@ fn parse_input(
         .filter(|l| !l.is_empty())
         .collect();
 
-    let mut i = 0;
-    while i < lines.len() {
-        let line = lines[i];
+    for line in lines {
         if line.contains('x') && line.contains(':') {
             let parts: Vec<&str> = line.split(':').collect();
             let dims: Vec<usize> = parts[0].split('x').map(|s| s.parse().unwrap()).collect();
@@ -111,108 +99,55 @@ fn parse_input(
                 .split_whitespace()
                 .map(|s| s.parse().unwrap())
                 .collect();
-            grids.append(&mut vec![(dims[0], dims[1], counts)]);
-        } else if line.ends_with(':') {
-            current_id = line[..line.len() - 1].parse().unwrap();
-            shapes.insert(current_id, Vec::new());
+            grids.push((dims[0], dims[1], counts));
+        } else if let Some(id) = line.strip_suffix(':') {
+            current_id = id.parse().unwrap();
+            shape_lines.insert(current_id, Vec::new());
         } else {
-            let val = line.chars().enumerate().fold(0u128, |acc, (j, c)| {
-                if c == '#' {
-                    acc | (1 << (line.len() - 1 - j))
-                } else {
-                    acc
-                }
-            });
-            shapes.get_mut(&current_id).unwrap().push((val, line.len()));
+            shape_lines.get_mut(&current_id).unwrap().push(line);
         }
-        i += 1;
     }
-    (shapes, grids)
-}
 
-fn generate_variations(
-    base_shapes: std::collections::HashMap<usize, Vec<(u128, usize)>>,
-) -> std::collections::HashMap<usize, Vec<Variation>> {
-    let mut variations = std::collections::HashMap::new();
+    let shapes = shape_lines
+        .into_iter()
+        .map(|(id, lines)| {
+            let shape = Grid2D::parse_padded_with(&lines.join("\n"), false, |_, _, c| {
+                Ok::<_, Infallible>(c == '#')
+            })
+            .unwrap();
 
-    for (sid, rows) in base_shapes {
-        let max_w = rows.iter().map(|r| r.1).max().unwrap_or(0);
-        let mut matrix = Vec::new();
-        for (val, w) in rows {
-            let mut row_bits = Vec::new();
-            for j in 0..max_w {
-                row_bits.push((val >> (w - 1 - j)) & 1 == 1);
-            }
-            matrix.push(row_bits);
-        }
+            (id, shape)
+        })
+        .collect();
 
-        let mut seen_hashes = FxHashSet::default();
-        let mut shape_vars = Vec::new();
-        let mut current = matrix;
-
-        for _ in 0..2 {
-            for _ in 0..4 {
-                let (mut min_r, mut max_r) = (current.len(), 0);
-                let (mut min_c, mut max_c) = (current[0].len(), 0);
-                let mut has_bits = false;
-
-                for r in 0..current.len() {
-                    for c in 0..current[0].len() {
-                        if current[r][c] {
-                            has_bits = true;
-                            min_r = min_r.min(r);
-                            max_r = max_r.max(r);
-                            min_c = min_c.min(c);
-                            max_c = max_c.max(c);
-                        }
-                    }
-                }
+    (shapes, grids)
+}
 
-                if has_bits {
-                    let mut int_rows = Vec::new();
-                    for r in min_r..=max_r {
-                        let mut r_val = 0u128;
-                        for c in min_c..=max_c {
-                            r_val = (r_val << 1) | if current[r][c] { 1 } else { 0 };
-                        }
-                        int_rows.push(r_val);
-                    }
+fn generate_variations(base_shapes: Shapes) -> std::collections::HashMap<usize, Vec<BitGrid>> {
+    let mut variations = std::collections::HashMap::new();
 
-                    if seen_hashes.insert(int_rows.clone()) {
-                        shape_vars.push(Variation {
-                            h: max_r - min_r + 1,
-                            w: max_c - min_c + 1,
-                            rows: int_rows,
-                        });
-                    }
-                }
+    for (sid, shape) in base_shapes {
+        let mut shape_vars: Vec<BitGrid> = shape
+            .crop_to(|&filled| filled)
+            .into_iter()
+            .flat_map(|cropped| cropped.symmetries())
+            .map(|variation| BitGrid::from(&variation))
+            .collect();
 
-                let h = current.len();
-                let w = current[0].len();
-                let mut next = vec![vec![false; h]; w];
-                for r in 0..h {
-                    for c in 0..w {
-                        next[c][h - 1 - r] = current[r][c];
-                    }
-                }
-                current = next;
-            }
-            current.reverse();
-        }
-        shape_vars.sort_by_key(|v| -(v.h as i32));
+        shape_vars.sort_by_key(|v| -(v.height() as i32));
         variations.insert(sid, shape_vars);
     }
     variations
 }
 
 fn is_space_sufficient(
-    grid: &[u128],
+    grid: &BitGrid,
     width: usize,
     height: usize,
     required_area: usize,
     min_item_area: usize,
 ) -> bool {
-    let used_area: usize = grid.iter().map(|row| row.count_ones() as usize).sum();
+    let used_area = grid.count_ones();
     let free_area = (width * height).saturating_sub(used_area);
     if free_area < required_area {
         return false;
@@ -221,52 +156,28 @@ fn is_space_sufficient(
         return true;
     }
 
-    let mut visited = vec![vec![false; width]; height];
+    let occupied = Grid2D::from_fn(width, height, |x, y| grid.test(x, y));
     let mut usable_free_area = 0;
 
-    for r in 0..height {
-        for c in 0..width {
-            let is_occupied = (grid[r] >> (width - 1 - c)) & 1 == 1;
-            if !is_occupied && !visited[r][c] {
-                let mut island_size = 0;
-                let mut q = VecDeque::new();
-                q.push_back((r, c));
-                visited[r][c] = true;
-
-                while let Some((curr_r, curr_c)) = q.pop_front() {
-                    island_size += 1;
-                    for (dr, dc) in [(0, 1), (0, -1), (1, 0), (-1, 0)] {
-                        let nr = curr_r as i32 + dr;
-                        let nc = curr_c as i32 + dc;
-                        if nr >= 0 && nr < height as i32 && nc >= 0 && nc < width as i32 {
-                            let (nr, nc) = (nr as usize, nc as usize);
-                            let n_occupied = (grid[nr] >> (width - 1 - nc)) & 1 == 1;
-                            if !n_occupied && !visited[nr][nc] {
-                                visited[nr][nc] = true;
-                                q.push_back((nr, nc));
-                            }
-                        }
-                    }
-                }
-                if island_size >= min_item_area {
-                    usable_free_area += island_size;
-                }
-                if usable_free_area >= required_area {
-                    return true;
-                }
-            }
+    for island in occupied.regions(|_, &occupied| !occupied, Connectivity::Cardinal) {
+        if island.len() >= min_item_area {
+            usable_free_area += island.len();
+        }
+        if usable_free_area >= required_area {
+            return true;
         }
     }
-    usable_free_area >= required_area
+
+    false
 }
 
 fn solve_recursive(
-    grid: &mut Vec<u128>,
+    grid: &mut BitGrid,
     items: &mut Vec<Item>,
     item_idx: usize,
     width: usize,
     height: usize,
-    variations: &std::collections::HashMap<usize, Vec<Variation>>,
+    variations: &std::collections::HashMap<usize, Vec<BitGrid>>,
     min_global_area: usize,
 ) -> bool {
     if item_idx == items.len() {
@@ -289,25 +200,14 @@ fn solve_recursive(
 
     let vars = &variations[&sid];
     for var in vars {
-        if var.h > height || var.w > width {
+        if var.height() > height || var.width() > width {
             continue;
         }
-        for r in start_r..=(height - var.h) {
+        for r in start_r..=(height - var.height()) {
             let c_begin = if r == start_r { start_c } else { 0 };
-            for c in c_begin..=(width - var.w) {
-                let shift = width - c - var.w;
-                let mut fits = true;
-                for i in 0..var.h {
-                    if (grid[r + i] & (var.rows[i] << shift)) != 0 {
-                        fits = false;
-                        break;
-                    }
-                }
-
-                if fits {
-                    for i in 0..var.h {
-                        grid[r + i] |= var.rows[i] << shift;
-                    }
+            for c in c_begin..=(width - var.width()) {
+                if grid.fits_at(var, c, r) {
+                    grid.or_at(var, c, r);
                     items[item_idx].placed_r = r;
                     items[item_idx].placed_c = c;
 
@@ -323,9 +223,7 @@ fn solve_recursive(
                         return true;
                     }
 
-                    for i in 0..var.h {
-                        grid[r + i] ^= var.rows[i] << shift;
-                    }
+                    grid.xor_at(var, c, r);
                 }
             }
         }
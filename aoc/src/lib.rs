@@ -1,7 +1,8 @@
 mod days;
-mod dsu;
-mod grid2d;
-mod search;
+mod rng;
+pub mod stress;
+pub mod util;
+mod validate;
 
 use std::{fmt::Display, fs, time::Duration};
 
@@ -22,9 +23,118 @@ impl Display for Solution {
     }
 }
 
+/// Structural problem found in an input by [`Day::validate`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    /// 1-based line number, when the problem is tied to a line.
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn at(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line: Some(line),
+            message: message.into(),
+        }
+    }
+
+    pub fn global(message: impl Into<String>) -> Self {
+        Self {
+            line: None,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {line}: {}", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// Name under which `Day::part1`/`Day::part2` are exposed next to the variants.
+pub const DEFAULT_IMPL: &str = "default";
+
+/// An alternative implementation of one part of a day, selectable by name.
+pub struct Variant {
+    pub name: &'static str,
+    pub part: u8,
+    pub solve: fn(&str) -> Solution,
+}
+
 pub trait Day: Sync + Send {
     fn part1(&self, input: &str) -> Solution;
     fn part2(&self, input: &str) -> Solution;
+
+    /// Other implementations of `part1`/`part2`, used to cross-check results.
+    fn variants(&self) -> Vec<Variant> {
+        Vec::new()
+    }
+
+    /// Brute-force implementations that only cope with the small inputs of
+    /// [`stress`]. They are checked like variants there, but left out of
+    /// [`Day::implementations`] so `compare` never runs them on a real input.
+    fn references(&self) -> Vec<Variant> {
+        Vec::new()
+    }
+
+    /// Random valid puzzle input for property testing, see [`stress`].
+    ///
+    /// `size` is a rough measure of the input length; the same `(seed, size)`
+    /// pair must always produce the same input.
+    fn generate_input(&self, _seed: u64, _size: usize) -> Option<String> {
+        None
+    }
+
+    /// Checks the assumptions the solver makes about the input, so a broken
+    /// input is reported instead of panicking somewhere inside the solver.
+    /// Returns `None` if the day has no validator.
+    fn validate(&self, _input: &str) -> Option<Vec<Diagnostic>> {
+        None
+    }
+
+    /// Names of every implementation of `part`, starting with [`DEFAULT_IMPL`].
+    fn implementations(&self, part: u8) -> Vec<&'static str> {
+        let mut names = vec![DEFAULT_IMPL];
+        names.extend(
+            self.variants()
+                .into_iter()
+                .filter(|v| v.part == part)
+                .map(|v| v.name),
+        );
+        names
+    }
+
+    /// [`Day::implementations`] of `part` followed by its references.
+    fn all_implementations(&self, part: u8) -> Vec<&'static str> {
+        let mut names = self.implementations(part);
+        names.extend(
+            self.references()
+                .into_iter()
+                .filter(|v| v.part == part)
+                .map(|v| v.name),
+        );
+        names
+    }
+
+    /// Runs the implementation or reference of `part` called `name`, if
+    /// there is one.
+    fn solve(&self, part: u8, name: &str, input: &str) -> Option<Solution> {
+        match (part, name) {
+            (1, DEFAULT_IMPL) => Some(self.part1(input)),
+            (2, DEFAULT_IMPL) => Some(self.part2(input)),
+            _ => self
+                .variants()
+                .into_iter()
+                .chain(self.references())
+                .find(|v| v.part == part && v.name == name)
+                .map(|v| (v.solve)(input)),
+        }
+    }
 }
 
 pub fn load_input(day: u8) -> String {
@@ -33,6 +143,13 @@ pub fn load_input(day: u8) -> String {
     fs::read_to_string(&path).expect(&format!("Failed to read input file: {}", path))
 }
 
+/// Stable hash (64-bit FNV-1a) of an input, used to tie saved results to it.
+pub fn fingerprint(input: &str) -> u64 {
+    input.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
 pub fn fmt_duration(d: Duration) -> String {
     if d.as_secs() >= 1 {
         let s = d.as_secs_f64();
@@ -0,0 +1,124 @@
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::{DEFAULT_IMPL, Day, Solution};
+
+/// How many neighbouring seeds are tried for each smaller size.
+const SMALLER_SEEDS: u64 = 16;
+
+/// Answer of one implementation, or the message it panicked with.
+pub type Outcome = Result<Solution, String>;
+
+/// A generated input on which a variant disagrees with the default implementation.
+#[derive(Debug)]
+pub struct Mismatch {
+    pub seed: u64,
+    pub size: usize,
+    pub part: u8,
+    pub implementation: &'static str,
+    pub input: String,
+    pub expected: Outcome,
+    pub found: Outcome,
+}
+
+/// Runs one implementation, turning a panic into an `Err` holding its message.
+pub fn run_caught(day: &dyn Day, part: u8, implementation: &str, input: &str) -> Outcome {
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        day.solve(part, implementation, input)
+            .expect("Unknown implementation")
+    }))
+    .map_err(|payload| {
+        if let Some(msg) = payload.downcast_ref::<&str>() {
+            msg.to_string()
+        } else if let Some(msg) = payload.downcast_ref::<String>() {
+            msg.clone()
+        } else {
+            "<non-string panic>".to_string()
+        }
+    })
+}
+
+/// Generates the input for `(seed, size)` and checks every variant of both
+/// parts against the default implementation.
+pub fn check(day: &dyn Day, seed: u64, size: usize) -> Option<Mismatch> {
+    let input = day
+        .generate_input(seed, size)
+        .expect("Day has no input generator");
+
+    for part in 1..=2 {
+        let names = day.all_implementations(part);
+        if names.len() < 2 {
+            continue;
+        }
+
+        let expected = run_caught(day, part, DEFAULT_IMPL, &input);
+
+        for &name in &names[1..] {
+            let found = run_caught(day, part, name, &input);
+
+            if found != expected {
+                return Some(Mismatch {
+                    seed,
+                    size,
+                    part,
+                    implementation: name,
+                    input,
+                    expected,
+                    found,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// Looks for a smaller failing case by generating fresh inputs, trying
+/// sizes from 1 upwards with the failing seed and a few of its neighbours.
+///
+/// This does not reduce the failing input itself: the returned case is a
+/// different input, which may fail for another reason than the original.
+/// Returns the original mismatch if nothing smaller fails.
+pub fn find_smaller_failure(day: &dyn Day, mismatch: Mismatch) -> Mismatch {
+    for size in 1..mismatch.size {
+        for offset in 0..SMALLER_SEEDS {
+            let seed = mismatch.seed.wrapping_add(offset);
+            if let Some(smaller) = check(day, seed, size) {
+                return smaller;
+            }
+        }
+    }
+
+    mismatch
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check;
+    use crate::DAYS;
+
+    /// Largest generated size for days whose variants are too slow for the
+    /// default one.
+    const MAX_SIZES: &[(u8, usize)] = &[(2, 10), (10, 5)];
+    const DEFAULT_MAX_SIZE: usize = 20;
+    const SEEDS: u64 = 30;
+
+    /// Quick smoke check of every generator, bigger runs are for `cli stress`.
+    #[test]
+    fn generated_inputs() {
+        for (&day, solver) in DAYS.iter() {
+            if solver.generate_input(0, 1).is_none() {
+                continue;
+            }
+
+            let max_size = MAX_SIZES
+                .iter()
+                .find(|&&(d, _)| d == day)
+                .map_or(DEFAULT_MAX_SIZE, |&(_, size)| size);
+
+            for seed in 0..SEEDS {
+                let mismatch = check(solver.as_ref(), seed, 1 + seed as usize % max_size);
+                assert!(mismatch.is_none(), "day {day:02}: {mismatch:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,38 @@
+/// Fixed-size 2D grid of bits, with shape placement operations.
+pub mod bitgrid;
+/// Disjoint-set unions: plain, keyed, with rollback and with potentials.
+pub mod dsu;
+/// Directed graphs over named nodes.
+pub mod graph;
+/// Dense 2D grid with parsing, searches, components, display and automata.
+pub mod grid2d;
+/// Sparse 2D grid over signed coordinates.
+pub mod infinite_grid;
+/// Memoization of recursive functions without recursion.
+pub mod memo;
+/// Minimum spanning trees.
+pub mod mst;
+/// Signed 2D points and directions.
+pub mod point;
+/// 2D prefix sums and difference arrays.
+pub mod prefix_sum;
+/// String algorithms on byte slices.
+pub mod search;
+
+/// Items most days need, to be imported with `use crate::util::prelude::*`
+/// from a day or `use aoc::util::prelude::*` from another crate.
+pub mod prelude {
+    pub use crate::{
+        Day, Diagnostic, Solution, Variant,
+        util::{
+            bitgrid::BitGrid,
+            dsu::{Dsu, KeyedDsu},
+            graph::Graph,
+            grid2d::{Connectivity, Grid2D},
+            infinite_grid::InfiniteGrid,
+            memo::Memo,
+            point::{Direction, Point, Vec2},
+            prefix_sum::{Difference2D, PrefixSum2D},
+        },
+    };
+}
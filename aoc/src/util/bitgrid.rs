@@ -0,0 +1,280 @@
+use crate::util::grid2d::Grid2D;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Boolean grid packed 64 cells per word, row by row, so whole rows can be
+/// combined a word at a time. Bits past the width of a row are always 0.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    words: Vec<u64>,
+    words_per_row: usize,
+    width: usize,
+    height: usize,
+}
+
+impl BitGrid {
+    pub fn new(width: usize, height: usize) -> Self {
+        let words_per_row = width.div_ceil(WORD_BITS);
+
+        Self {
+            words: vec![0; words_per_row * height],
+            words_per_row,
+            width,
+            height,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn locate(&self, x: usize, y: usize) -> (usize, u64) {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) out of bounds of a {}x{} grid",
+            self.width,
+            self.height
+        );
+        (y * self.words_per_row + x / WORD_BITS, 1 << (x % WORD_BITS))
+    }
+
+    pub fn set(&mut self, x: usize, y: usize) {
+        let (word, bit) = self.locate(x, y);
+        self.words[word] |= bit;
+    }
+
+    pub fn clear(&mut self, x: usize, y: usize) {
+        let (word, bit) = self.locate(x, y);
+        self.words[word] &= !bit;
+    }
+
+    pub fn test(&self, x: usize, y: usize) -> bool {
+        let (word, bit) = self.locate(x, y);
+        self.words[word] & bit != 0
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Positions of the set cells, row by row.
+    pub fn iter_ones(&self) -> Ones<'_> {
+        Ones {
+            grid: self,
+            word: 0,
+            rest: self.words.first().copied().unwrap_or(0),
+        }
+    }
+
+    /// Sets every cell that is set in `other` placed with its top-left corner
+    /// at `(x, y)`. Cells of `other` falling outside are dropped.
+    pub fn or_at(&mut self, other: &BitGrid, x: usize, y: usize) {
+        self.apply_at(other, x, y, |word, bits, _| *word |= bits);
+    }
+
+    /// Clears the cells covered by `other` at `(x, y)` that are not set in
+    /// it. Cells outside of `other`'s rectangle are left untouched.
+    pub fn and_at(&mut self, other: &BitGrid, x: usize, y: usize) {
+        self.apply_at(other, x, y, |word, bits, covered| *word &= bits | !covered);
+    }
+
+    /// Toggles every cell that is set in `other` placed at `(x, y)`.
+    pub fn xor_at(&mut self, other: &BitGrid, x: usize, y: usize) {
+        self.apply_at(other, x, y, |word, bits, _| *word ^= bits);
+    }
+
+    /// Whether `other` placed at `(x, y)` has a set cell on top of one of ours.
+    pub fn intersects_at(&self, other: &BitGrid, x: usize, y: usize) -> bool {
+        !self.for_each_placed(other, x, y, |word, bits, _| self.words[word] & bits == 0)
+    }
+
+    /// Whether `shape` placed at `(x, y)` lies fully inside the grid without
+    /// overlapping any set cell.
+    pub fn fits_at(&self, shape: &BitGrid, x: usize, y: usize) -> bool {
+        x + shape.width <= self.width
+            && y + shape.height <= self.height
+            && !self.intersects_at(shape, x, y)
+    }
+
+    fn apply_at<F>(&mut self, other: &BitGrid, x: usize, y: usize, mut op: F)
+    where
+        F: FnMut(&mut u64, u64, u64),
+    {
+        // Only the dimensions are read while placing, so the words can be
+        // borrowed mutably on the side
+        let mut words = std::mem::take(&mut self.words);
+        self.for_each_placed(other, x, y, |word, bits, covered| {
+            op(&mut words[word], bits, covered);
+            true
+        });
+        self.words = words;
+    }
+
+    /// Calls `f(word, bits, covered)` for every word of this grid overlapped
+    /// by `other` placed at `(x, y)`, with `other`'s bits shifted into place
+    /// and the mask of the cells its rectangle covers, both clipped to our
+    /// width. Stops as soon as `f` returns false, and returns false if it did.
+    fn for_each_placed<F>(&self, other: &BitGrid, x: usize, y: usize, mut f: F) -> bool
+    where
+        F: FnMut(usize, u64, u64) -> bool,
+    {
+        if x >= self.width {
+            return true;
+        }
+
+        let last_mask = match self.width % WORD_BITS {
+            0 => u64::MAX,
+            rem => (1 << rem) - 1,
+        };
+        let shift = x % WORD_BITS;
+
+        for row in 0..other.height.min(self.height.saturating_sub(y)) {
+            let row_start = (y + row) * self.words_per_row;
+
+            for w in 0..other.words_per_row {
+                let bits = other.words[row * other.words_per_row + w];
+                let covered = match other.width - w * WORD_BITS {
+                    n if n >= WORD_BITS => u64::MAX,
+                    n => (1 << n) - 1,
+                };
+
+                let target = x / WORD_BITS + w;
+                let halves = [
+                    (target, bits << shift, covered << shift),
+                    (
+                        target + 1,
+                        bits.checked_shr((WORD_BITS - shift) as u32).unwrap_or(0),
+                        covered.checked_shr((WORD_BITS - shift) as u32).unwrap_or(0),
+                    ),
+                ];
+
+                for (target, bits, covered) in halves {
+                    if target >= self.words_per_row || covered == 0 {
+                        continue;
+                    }
+
+                    let mask = if target + 1 == self.words_per_row {
+                        last_mask
+                    } else {
+                        u64::MAX
+                    };
+                    if !f(row_start + target, bits & mask, covered & mask) {
+                        return false;
+                    }
+                }
+            }
+        }
+
+        true
+    }
+}
+
+impl From<&Grid2D<bool>> for BitGrid {
+    fn from(grid: &Grid2D<bool>) -> Self {
+        let mut bits = BitGrid::new(grid.width(), grid.height());
+        for ((x, y), &cell) in grid {
+            if cell {
+                bits.set(x, y);
+            }
+        }
+        bits
+    }
+}
+
+#[derive(Debug)]
+pub struct Ones<'a> {
+    grid: &'a BitGrid,
+    word: usize,
+    rest: u64,
+}
+
+impl Iterator for Ones<'_> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.rest == 0 {
+            self.word += 1;
+            self.rest = *self.grid.words.get(self.word)?;
+        }
+
+        let bit = self.rest.trailing_zeros() as usize;
+        self.rest &= self.rest - 1;
+
+        let words_per_row = self.grid.words_per_row;
+        Some((
+            (self.word % words_per_row) * WORD_BITS + bit,
+            self.word / words_per_row,
+        ))
+    }
+}
+
+impl<'a> IntoIterator for &'a BitGrid {
+    type Item = (usize, usize);
+    type IntoIter = Ones<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_ones()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::{bitgrid::BitGrid, grid2d::Grid2D};
+
+    fn from_str(input: &str) -> BitGrid {
+        BitGrid::from(&Grid2D::from_bytes(input).unwrap().map(|&c| c == b'#'))
+    }
+
+    #[test]
+    fn set_clear_test() {
+        let mut grid = BitGrid::new(130, 2);
+        grid.set(0, 0);
+        grid.set(64, 1);
+        grid.set(129, 1);
+        grid.clear(0, 0);
+
+        assert!(!grid.test(0, 0));
+        assert!(grid.test(64, 1));
+        assert!(grid.test(129, 1));
+        assert_eq!(grid.count_ones(), 2);
+        assert_eq!(
+            grid.iter_ones().collect::<Vec<_>>(),
+            vec![(64, 1), (129, 1)]
+        );
+    }
+
+    #[test]
+    fn shifted_ops_across_words() {
+        let shape = from_str("##\n.#");
+        let mut grid = BitGrid::new(200, 3);
+
+        grid.or_at(&shape, 63, 1);
+        assert!(grid.test(63, 1) && grid.test(64, 1) && grid.test(64, 2));
+        assert_eq!(grid.count_ones(), 3);
+
+        assert!(!grid.fits_at(&shape, 62, 0));
+        assert!(grid.fits_at(&shape, 61, 0));
+        assert!(!grid.fits_at(&shape, 199, 0));
+        assert!(!grid.fits_at(&shape, 0, 2));
+
+        // Clipped at the right edge
+        grid.or_at(&shape, 199, 0);
+        assert!(grid.test(199, 0));
+        assert_eq!(grid.count_ones(), 4);
+
+        grid.xor_at(&shape, 63, 1);
+        assert_eq!(grid.count_ones(), 1);
+    }
+
+    #[test]
+    fn and_only_touches_covered_cells() {
+        let mut grid = from_str("####\n####");
+        grid.and_at(&from_str("#.\n.#"), 1, 0);
+
+        assert_eq!(grid, from_str("##.#\n#.##"));
+    }
+}
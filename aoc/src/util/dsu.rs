@@ -0,0 +1,602 @@
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt::{Debug, Display},
+    hash::Hash,
+};
+
+use rustc_hash::FxHashMap;
+
+/// How many components there are of each size, kept up to date on every
+/// union so the largest ones can be read without a scan.
+#[derive(Debug, Clone, Default)]
+struct SizeCounts(BTreeMap<usize, usize>);
+
+impl SizeCounts {
+    fn singletons(n: usize) -> Self {
+        let mut counts = Self::default();
+        if n > 0 {
+            counts.0.insert(1, n);
+        }
+        counts
+    }
+
+    fn add(&mut self, size: usize) {
+        *self.0.entry(size).or_default() += 1;
+    }
+
+    fn remove(&mut self, size: usize) {
+        let count = self.0.get_mut(&size).expect("No component of this size");
+        *count -= 1;
+        if *count == 0 {
+            self.0.remove(&size);
+        }
+    }
+
+    /// Records that components of sizes `a` and `b` became one.
+    fn merge(&mut self, a: usize, b: usize) {
+        self.remove(a);
+        self.remove(b);
+        self.add(a + b);
+    }
+
+    /// Undoes [`SizeCounts::merge`].
+    fn split(&mut self, a: usize, b: usize) {
+        self.remove(a + b);
+        self.add(a);
+        self.add(b);
+    }
+
+    /// Sizes of the `k` largest components, largest first.
+    fn largest(&self, k: usize) -> Vec<usize> {
+        self.0
+            .iter()
+            .rev()
+            .flat_map(|(&size, &count)| std::iter::repeat_n(size, count))
+            .take(k)
+            .collect()
+    }
+}
+
+pub struct Dsu {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    components: usize,
+    sizes: SizeCounts,
+}
+
+impl Dsu {
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            components: n,
+            sizes: SizeCounts::singletons(n),
+        }
+    }
+
+    /// Adds a new element in its own component and returns its index.
+    pub fn push(&mut self) -> usize {
+        let i = self.parent.len();
+        self.parent.push(i);
+        self.size.push(1);
+        self.components += 1;
+        self.sizes.add(1);
+        i
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    pub fn find(&mut self, mut i: usize) -> usize {
+        while self.parent[i] != i {
+            // Path halving: every other node on the way skips its parent
+            self.parent[i] = self.parent[self.parent[i]];
+            i = self.parent[i];
+        }
+        i
+    }
+
+    /// Merges the components of `i` and `j`, returns false if they were
+    /// already the same.
+    pub fn union(&mut self, i: usize, j: usize) -> bool {
+        let root_i = self.find(i);
+        let root_j = self.find(j);
+
+        if root_i == root_j {
+            return false;
+        }
+
+        self.sizes.merge(self.size[root_i], self.size[root_j]);
+
+        // Union by size
+        if self.size[root_i] < self.size[root_j] {
+            self.parent[root_i] = root_j;
+            self.size[root_j] += self.size[root_i];
+        } else {
+            self.parent[root_j] = root_i;
+            self.size[root_i] += self.size[root_j];
+        }
+        self.components -= 1;
+
+        true
+    }
+
+    pub fn same(&mut self, i: usize, j: usize) -> bool {
+        self.find(i) == self.find(j)
+    }
+
+    /// Number of elements in the component of `i`.
+    pub fn size_of(&mut self, i: usize) -> usize {
+        let root = self.find(i);
+        self.size[root]
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    /// Members of every component, each sorted, ordered by smallest member.
+    pub fn components(&mut self) -> impl Iterator<Item = Vec<usize>> {
+        let mut members: Vec<Vec<usize>> = vec![Vec::new(); self.parent.len()];
+        for i in 0..self.parent.len() {
+            let root = self.find(i);
+            members[root].push(i);
+        }
+
+        members.sort_unstable_by_key(|m| m.first().copied().unwrap_or(usize::MAX));
+        members.into_iter().filter(|m| !m.is_empty())
+    }
+
+    /// Sizes of the `k` largest components, largest first.
+    pub fn largest_components(&self, k: usize) -> Vec<usize> {
+        self.sizes.largest(k)
+    }
+}
+
+/// [`Dsu`] over arbitrary keys, which are given an index the first time
+/// they are seen.
+pub struct KeyedDsu<K> {
+    dsu: Dsu,
+    indices: FxHashMap<K, usize>,
+    keys: Vec<K>,
+}
+
+impl<K> Default for KeyedDsu<K> {
+    fn default() -> Self {
+        Self {
+            dsu: Dsu::new(0),
+            indices: FxHashMap::default(),
+            keys: Vec::new(),
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone> KeyedDsu<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Index of `key`, adding it as its own component if it's new.
+    pub fn index(&mut self, key: &K) -> usize {
+        if let Some(&i) = self.indices.get(key) {
+            return i;
+        }
+
+        let i = self.dsu.push();
+        self.indices.insert(key.clone(), i);
+        self.keys.push(key.clone());
+        i
+    }
+
+    pub fn key(&self, index: usize) -> &K {
+        &self.keys[index]
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.indices.contains_key(key)
+    }
+
+    /// Representative of the component of `key`, `None` if it was never seen.
+    pub fn find(&mut self, key: &K) -> Option<&K> {
+        let &i = self.indices.get(key)?;
+        let root = self.dsu.find(i);
+        Some(&self.keys[root])
+    }
+
+    pub fn union(&mut self, a: &K, b: &K) -> bool {
+        let (i, j) = (self.index(a), self.index(b));
+        self.dsu.union(i, j)
+    }
+
+    /// Whether `a` and `b` are in the same component, false if either was
+    /// never seen.
+    pub fn same(&mut self, a: &K, b: &K) -> bool {
+        match (self.indices.get(a), self.indices.get(b)) {
+            (Some(&i), Some(&j)) => self.dsu.same(i, j),
+            _ => false,
+        }
+    }
+
+    pub fn size_of(&mut self, key: &K) -> Option<usize> {
+        let &i = self.indices.get(key)?;
+        Some(self.dsu.size_of(i))
+    }
+
+    /// Number of components among the keys seen so far.
+    pub fn component_count(&self) -> usize {
+        self.dsu.component_count()
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Keys of every component, in the order they were first seen.
+    pub fn components(&mut self) -> impl Iterator<Item = Vec<&K>> {
+        let keys = &self.keys;
+        self.dsu
+            .components()
+            .map(move |members| members.into_iter().map(|i| &keys[i]).collect())
+    }
+
+    pub fn largest_components(&self, k: usize) -> Vec<usize> {
+        self.dsu.largest_components(k)
+    }
+}
+
+/// Position of a [`RollbackDsu`] history that can be returned to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Snapshot(usize);
+
+/// [`Dsu`] whose unions can be undone. It has no path compression, so
+/// `find` is O(log n) thanks to union by size alone.
+pub struct RollbackDsu {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    components: usize,
+    sizes: SizeCounts,
+    /// `(child, root)` of every successful union, in order.
+    history: Vec<(usize, usize)>,
+}
+
+impl RollbackDsu {
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            components: n,
+            sizes: SizeCounts::singletons(n),
+            history: Vec::new(),
+        }
+    }
+
+    pub fn find(&self, mut i: usize) -> usize {
+        while self.parent[i] != i {
+            i = self.parent[i];
+        }
+        i
+    }
+
+    pub fn union(&mut self, i: usize, j: usize) -> bool {
+        let (mut root_i, mut root_j) = (self.find(i), self.find(j));
+
+        if root_i == root_j {
+            return false;
+        }
+
+        if self.size[root_i] < self.size[root_j] {
+            std::mem::swap(&mut root_i, &mut root_j);
+        }
+        self.sizes.merge(self.size[root_i], self.size[root_j]);
+        self.parent[root_j] = root_i;
+        self.size[root_i] += self.size[root_j];
+        self.components -= 1;
+        self.history.push((root_j, root_i));
+
+        true
+    }
+
+    pub fn same(&self, i: usize, j: usize) -> bool {
+        self.find(i) == self.find(j)
+    }
+
+    pub fn size_of(&self, i: usize) -> usize {
+        self.size[self.find(i)]
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    /// Sizes of the `k` largest components, largest first.
+    pub fn largest_components(&self, k: usize) -> Vec<usize> {
+        self.sizes.largest(k)
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot(self.history.len())
+    }
+
+    /// Undoes every union made since `snapshot` was taken.
+    pub fn rollback(&mut self, snapshot: Snapshot) {
+        while self.history.len() > snapshot.0 {
+            let (child, root) = self.history.pop().unwrap();
+            self.parent[child] = child;
+            self.size[root] -= self.size[child];
+            self.sizes.split(self.size[root], self.size[child]);
+            self.components += 1;
+        }
+    }
+}
+
+/// Relative value between two elements of a [`WeightedDsu`]: any
+/// commutative group, like integers under addition or parities under XOR.
+pub trait Potential: Copy + Eq {
+    const ZERO: Self;
+
+    fn combine(self, other: Self) -> Self;
+    fn inverse(self) -> Self;
+}
+
+impl Potential for i64 {
+    const ZERO: Self = 0;
+
+    fn combine(self, other: Self) -> Self {
+        self + other
+    }
+
+    fn inverse(self) -> Self {
+        -self
+    }
+}
+
+impl Potential for bool {
+    const ZERO: Self = false;
+
+    fn combine(self, other: Self) -> Self {
+        self ^ other
+    }
+
+    fn inverse(self) -> Self {
+        self
+    }
+}
+
+/// Returned by [`WeightedDsu::union`] when a relation disagrees with the
+/// ones already merged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Contradiction<W> {
+    /// Difference implied by the previous relations.
+    pub known: W,
+    /// Difference that was asked for.
+    pub claimed: W,
+}
+
+impl<W: Debug> Display for Contradiction<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "difference {:?} contradicts the known difference {:?}",
+            self.claimed, self.known
+        )
+    }
+}
+
+impl<W: Debug> Error for Contradiction<W> {}
+
+/// [`Dsu`] that also tracks the difference between every element and its
+/// root, so relations like `a - b = k` can be merged and checked.
+pub struct WeightedDsu<W> {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    /// Difference between an element and its parent.
+    potential: Vec<W>,
+    components: usize,
+}
+
+impl<W: Potential> WeightedDsu<W> {
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            potential: vec![W::ZERO; n],
+            components: n,
+        }
+    }
+
+    /// Root of `i` and the difference between `i` and that root.
+    pub fn find(&mut self, i: usize) -> (usize, W) {
+        let mut root = i;
+        let mut total = W::ZERO;
+        while self.parent[root] != root {
+            total = total.combine(self.potential[root]);
+            root = self.parent[root];
+        }
+
+        // Point the whole path at the root, peeling off each node's own
+        // potential to get the next one's
+        let mut node = i;
+        let mut remaining = total;
+        while node != root {
+            let next = self.parent[node];
+            let own = self.potential[node];
+
+            self.parent[node] = root;
+            self.potential[node] = remaining;
+            remaining = remaining.combine(own.inverse());
+            node = next;
+        }
+
+        (root, total)
+    }
+
+    /// Records that `a - b = diff`. Returns whether two components were
+    /// merged, or the contradiction if `a` and `b` were already related
+    /// differently.
+    pub fn union(&mut self, a: usize, b: usize, diff: W) -> Result<bool, Contradiction<W>> {
+        let (root_a, to_a) = self.find(a);
+        let (root_b, to_b) = self.find(b);
+
+        if root_a == root_b {
+            let known = to_a.combine(to_b.inverse());
+            return if known == diff {
+                Ok(false)
+            } else {
+                Err(Contradiction {
+                    known,
+                    claimed: diff,
+                })
+            };
+        }
+
+        // root_a - root_b = diff - to_a + to_b
+        let roots_diff = diff.combine(to_a.inverse()).combine(to_b);
+        if self.size[root_a] < self.size[root_b] {
+            self.parent[root_a] = root_b;
+            self.potential[root_a] = roots_diff;
+            self.size[root_b] += self.size[root_a];
+        } else {
+            self.parent[root_b] = root_a;
+            self.potential[root_b] = roots_diff.inverse();
+            self.size[root_a] += self.size[root_b];
+        }
+        self.components -= 1;
+
+        Ok(true)
+    }
+
+    /// `a - b`, if they are related.
+    pub fn diff(&mut self, a: usize, b: usize) -> Option<W> {
+        let (root_a, to_a) = self.find(a);
+        let (root_b, to_b) = self.find(b);
+
+        (root_a == root_b).then(|| to_a.combine(to_b.inverse()))
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::dsu::{Contradiction, Dsu, KeyedDsu, RollbackDsu, WeightedDsu};
+
+    #[test]
+    fn components() {
+        let mut dsu = Dsu::new(6);
+
+        assert!(dsu.union(0, 3));
+        assert!(dsu.union(4, 3));
+        assert!(dsu.union(1, 2));
+        assert!(!dsu.union(0, 4));
+
+        assert!(dsu.same(0, 4));
+        assert!(!dsu.same(0, 1));
+        assert_eq!(dsu.size_of(4), 3);
+        assert_eq!(dsu.component_count(), 3);
+        assert_eq!(dsu.largest_components(2), vec![3, 2]);
+        assert_eq!(dsu.largest_components(5), vec![3, 2, 1]);
+        assert_eq!(
+            dsu.components().collect::<Vec<_>>(),
+            vec![vec![0, 3, 4], vec![1, 2], vec![5]]
+        );
+
+        dsu.push();
+        assert_eq!(dsu.largest_components(5), vec![3, 2, 1, 1]);
+    }
+
+    #[test]
+    fn keyed() {
+        let mut dsu = KeyedDsu::new();
+
+        assert!(dsu.union(&"you", &"bbb"));
+        assert!(dsu.union(&"ccc", &"ddd"));
+        assert!(dsu.union(&"bbb", &"ddd"));
+        assert!(!dsu.union(&"you", &"ccc"));
+
+        assert!(dsu.same(&"you", &"ddd"));
+        assert!(!dsu.same(&"you", &"out"));
+        assert_eq!(dsu.size_of(&"out"), None);
+        assert_eq!(dsu.find(&"out"), None);
+        assert_eq!(dsu.len(), 4);
+
+        dsu.index(&"out");
+        assert!(!dsu.same(&"you", &"out"));
+        assert_eq!(dsu.len(), 5);
+        assert_eq!(dsu.component_count(), 2);
+        assert_eq!(dsu.size_of(&"ccc"), Some(4));
+        let root = *dsu.find(&"ddd").unwrap();
+        assert_eq!(dsu.find(&"you"), Some(&root));
+        assert_eq!(
+            dsu.components().collect::<Vec<_>>(),
+            vec![vec![&"you", &"bbb", &"ccc", &"ddd"], vec![&"out"]]
+        );
+    }
+
+    #[test]
+    fn rollback() {
+        let mut dsu = RollbackDsu::new(5);
+        dsu.union(0, 1);
+        let snapshot = dsu.snapshot();
+
+        dsu.union(2, 3);
+        dsu.union(1, 3);
+        assert!(!dsu.union(0, 2));
+        assert_eq!(dsu.size_of(2), 4);
+        assert_eq!(dsu.component_count(), 2);
+        assert_eq!(dsu.largest_components(3), vec![4, 1]);
+
+        dsu.rollback(snapshot);
+        assert!(dsu.same(0, 1));
+        assert!(!dsu.same(1, 3));
+        assert!(!dsu.same(2, 3));
+        assert_eq!(dsu.size_of(0), 2);
+        assert_eq!(dsu.component_count(), 4);
+        assert_eq!(dsu.largest_components(2), vec![2, 1]);
+    }
+
+    #[test]
+    fn weighted() {
+        let mut dsu = WeightedDsu::new(5);
+
+        assert_eq!(dsu.union(0, 1, 3), Ok(true));
+        assert_eq!(dsu.union(2, 1, -4), Ok(true));
+        assert_eq!(dsu.union(3, 4, 10), Ok(true));
+        assert_eq!(dsu.union(4, 2, 1), Ok(true));
+
+        assert_eq!(dsu.diff(0, 2), Some(7));
+        assert_eq!(dsu.diff(3, 0), Some(4));
+        assert_eq!(dsu.union(3, 1, 7), Ok(false));
+        assert_eq!(
+            dsu.union(1, 3, 1),
+            Err(Contradiction {
+                known: -7,
+                claimed: 1
+            })
+        );
+        assert_eq!(dsu.component_count(), 1);
+    }
+
+    #[test]
+    fn parity() {
+        // Triangle where every pair must differ: impossible to 2-color
+        let mut dsu = WeightedDsu::new(3);
+
+        assert_eq!(dsu.union(0, 1, true), Ok(true));
+        assert_eq!(dsu.union(1, 2, true), Ok(true));
+        assert_eq!(dsu.diff(0, 2), Some(false));
+        assert!(dsu.union(2, 0, true).is_err());
+    }
+}
@@ -0,0 +1,670 @@
+use std::{collections::VecDeque, error::Error, fmt::Display, iter::Sum, ops::Add, str::FromStr};
+
+use rustc_hash::FxHashMap;
+
+/// Directed graph over named nodes. Names are interned to dense ids
+/// `0..len()`, in order of first appearance.
+#[derive(Debug, Clone, Default)]
+pub struct Graph {
+    names: Vec<String>,
+    ids: FxHashMap<String, usize>,
+    edges: Vec<Vec<usize>>,
+}
+
+/// Error returned when parsing a [`Graph`] from text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseGraphError {
+    /// 1-based number of the line without a `:`.
+    pub line: usize,
+}
+
+impl Display for ParseGraphError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Line {} is not `name: child child ...`", self.line)
+    }
+}
+
+impl Error for ParseGraphError {}
+
+/// Parses lines of `name: child child ...`, one edge from `name` to every
+/// child. Children don't need a line of their own.
+impl FromStr for Graph {
+    type Err = ParseGraphError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut graph = Graph::new();
+
+        for (i, line) in s.lines().enumerate() {
+            let (name, children) = line
+                .split_once(':')
+                .ok_or(ParseGraphError { line: i + 1 })?;
+
+            let from = graph.add_node(name.trim());
+            for child in children.split_whitespace() {
+                let to = graph.add_node(child);
+                graph.add_edge(from, to);
+            }
+        }
+
+        Ok(graph)
+    }
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Id of the node called `name`, added if it doesn't exist yet.
+    pub fn add_node(&mut self, name: &str) -> usize {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+
+        let id = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.edges.push(Vec::new());
+        id
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize) {
+        self.edges[from].push(to);
+    }
+
+    pub fn id(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: usize) -> &str {
+        &self.names[id]
+    }
+
+    /// Number of nodes.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Targets of the edges leaving `id`.
+    pub fn neighbors(&self, id: usize) -> &[usize] {
+        &self.edges[id]
+    }
+
+    /// Same graph with every edge turned around. Ids are kept.
+    pub fn reversed(&self) -> Self {
+        let mut edges = vec![Vec::new(); self.len()];
+        for (from, targets) in self.edges.iter().enumerate() {
+            for &to in targets {
+                edges[to].push(from);
+            }
+        }
+
+        Self {
+            names: self.names.clone(),
+            ids: self.ids.clone(),
+            edges,
+        }
+    }
+
+    /// Which nodes can be reached from `from`, itself included.
+    pub fn reachable(&self, from: usize) -> Vec<bool> {
+        let mut seen = vec![false; self.len()];
+        let mut queue = VecDeque::from([from]);
+        seen[from] = true;
+
+        while let Some(u) = queue.pop_front() {
+            for &v in &self.edges[u] {
+                if !seen[v] {
+                    seen[v] = true;
+                    queue.push_back(v);
+                }
+            }
+        }
+
+        seen
+    }
+
+    /// Every node ordered so that edges only go forward, or `None` if the
+    /// graph has a cycle. Uses Kahn's algorithm.
+    pub fn topological_sort(&self) -> Option<Vec<usize>> {
+        let mut in_degree = vec![0; self.len()];
+        for &v in self.edges.iter().flatten() {
+            in_degree[v] += 1;
+        }
+
+        let mut order: Vec<usize> = (0..self.len()).filter(|&u| in_degree[u] == 0).collect();
+        let mut i = 0;
+        while let Some(&u) = order.get(i) {
+            for &v in &self.edges[u] {
+                in_degree[v] -= 1;
+                if in_degree[v] == 0 {
+                    order.push(v);
+                }
+            }
+            i += 1;
+        }
+
+        (order.len() == self.len()).then_some(order)
+    }
+
+    /// Nodes of some cycle, in the order of its edges, if there is one.
+    pub fn find_cycle(&self) -> Option<Vec<usize>> {
+        // Position on the DFS path of the nodes being explored
+        let mut on_path: Vec<Option<usize>> = vec![None; self.len()];
+        let mut done = vec![false; self.len()];
+
+        for root in 0..self.len() {
+            if done[root] {
+                continue;
+            }
+
+            let mut path = vec![(root, 0)];
+            on_path[root] = Some(0);
+
+            while let Some(&mut (u, ref mut next)) = path.last_mut() {
+                let Some(&v) = self.edges[u].get(*next) else {
+                    on_path[u] = None;
+                    done[u] = true;
+                    path.pop();
+                    continue;
+                };
+                *next += 1;
+
+                if let Some(start) = on_path[v] {
+                    return Some(path[start..].iter().map(|&(node, _)| node).collect());
+                }
+                if !done[v] {
+                    on_path[v] = Some(path.len());
+                    path.push((v, 0));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Strongly connected components with Tarjan's algorithm, in reverse
+    /// topological order: edges between components only go from a component
+    /// to an earlier one.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        const UNSEEN: usize = usize::MAX;
+        let mut index = vec![UNSEEN; self.len()];
+        let mut low = vec![0; self.len()];
+        let mut on_stack = vec![false; self.len()];
+        let mut stack = Vec::new();
+        let mut components = Vec::new();
+        let mut counter = 0;
+
+        for root in 0..self.len() {
+            if index[root] != UNSEEN {
+                continue;
+            }
+
+            let mut calls = vec![(root, 0)];
+            index[root] = counter;
+            low[root] = counter;
+            counter += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            while let Some(&mut (u, ref mut next)) = calls.last_mut() {
+                if let Some(&v) = self.edges[u].get(*next) {
+                    *next += 1;
+                    if index[v] == UNSEEN {
+                        index[v] = counter;
+                        low[v] = counter;
+                        counter += 1;
+                        stack.push(v);
+                        on_stack[v] = true;
+                        calls.push((v, 0));
+                    } else if on_stack[v] {
+                        low[u] = low[u].min(index[v]);
+                    }
+                    continue;
+                }
+
+                calls.pop();
+                if let Some(&(parent, _)) = calls.last() {
+                    low[parent] = low[parent].min(low[u]);
+                }
+                if low[u] == index[u] {
+                    let mut component = Vec::new();
+                    loop {
+                        let v = stack.pop().unwrap();
+                        on_stack[v] = false;
+                        component.push(v);
+                        if v == u {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+
+        components
+    }
+
+    /// The DAG of the strongly connected components.
+    pub fn condensation(&self) -> Condensation {
+        let mut components = self.strongly_connected_components();
+        components.reverse();
+
+        let mut component_of = vec![0; self.len()];
+        for (c, nodes) in components.iter().enumerate() {
+            for &u in nodes {
+                component_of[u] = c;
+            }
+        }
+
+        let mut edges = vec![Vec::new(); components.len()];
+        let mut cyclic: Vec<bool> = components.iter().map(|nodes| nodes.len() > 1).collect();
+        for (u, targets) in self.edges.iter().enumerate() {
+            let c = component_of[u];
+            for &v in targets {
+                if component_of[v] == c {
+                    cyclic[c] = true;
+                } else {
+                    edges[c].push(component_of[v]);
+                }
+            }
+        }
+        for targets in &mut edges {
+            targets.sort_unstable();
+            targets.dedup();
+        }
+
+        Condensation {
+            components,
+            component_of,
+            edges,
+            cyclic,
+        }
+    }
+
+    /// Number of walks from `from` to `to`. Walks may go through `to` before
+    /// ending there, so the count is infinite as soon as a cycle can be
+    /// reached from `from` and leads to `to`.
+    ///
+    /// # Panics
+    /// If a finite count overflows `u64`.
+    pub fn count_paths(&self, from: usize, to: usize) -> PathCount {
+        self.count_paths_through(from, to, &[])
+    }
+
+    /// Number of walks from `from` to `to` that visit every node of
+    /// `waypoints`, in any order. Infinite when one of them can go around a
+    /// cycle, see [`Graph::count_paths`].
+    ///
+    /// Counts are kept per set of waypoints already visited, so this takes
+    /// O(2^k × (V + E)) for `k` waypoints. Panics like [`Graph::count_paths`]
+    /// on overflow.
+    pub fn count_paths_through(&self, from: usize, to: usize, waypoints: &[usize]) -> PathCount {
+        let sets = 1 << waypoints.len();
+        let all = sets - 1;
+        let mut bit = vec![0; self.len()];
+        for (i, &w) in waypoints.iter().enumerate() {
+            bit[w] |= 1 << i;
+        }
+
+        // `paths[u * sets + seen]`: walks from `u` to `to` that complete the
+        // waypoints when the ones in `seen` were visited before `u`
+        let mut paths = vec![PathCount::Finite(0); self.len() * sets];
+        let condensation = self.condensation();
+
+        for (c, nodes) in condensation.components.iter().enumerate().rev() {
+            if !condensation.cyclic[c] {
+                let u = nodes[0];
+                for seen in 0..sets {
+                    let seen_now = seen | bit[u];
+                    paths[u * sets + seen] = if u == to {
+                        PathCount::Finite(u64::from(seen_now == all))
+                    } else {
+                        self.edges[u]
+                            .iter()
+                            .map(|&v| paths[v * sets + seen_now])
+                            .sum()
+                    };
+                }
+                continue;
+            }
+
+            // Inside a cycle every node of the component can be visited in
+            // any order, and as many times as wanted
+            let inside = nodes.iter().fold(0, |acc, &u| acc | bit[u]);
+            for seen in 0..sets {
+                let seen_now = seen | inside;
+                let completes = (nodes.contains(&to) && seen_now == all)
+                    || nodes.iter().any(|&u| {
+                        self.edges[u].iter().any(|&v| {
+                            condensation.component_of[v] != c
+                                && paths[v * sets + seen_now] != PathCount::Finite(0)
+                        })
+                    });
+
+                let count = if completes {
+                    PathCount::Infinite
+                } else {
+                    PathCount::Finite(0)
+                };
+                for &u in nodes {
+                    paths[u * sets + seen] = count;
+                }
+            }
+        }
+
+        paths[from * sets]
+    }
+
+    /// Immediate dominator of every node reachable from `root`: the closest
+    /// node that every path from `root` goes through. `None` for `root`
+    /// itself and for unreachable nodes.
+    ///
+    /// Uses the iterative algorithm of Cooper, Harvey and Kennedy.
+    pub fn dominators(&self, root: usize) -> Vec<Option<usize>> {
+        let mut order = self.postorder(root);
+        order.reverse();
+
+        // Position in reverse postorder, dominators always come first
+        let mut rank = vec![usize::MAX; self.len()];
+        for (i, &u) in order.iter().enumerate() {
+            rank[u] = i;
+        }
+
+        let predecessors = self.reversed();
+        let mut idom = vec![None; self.len()];
+        idom[root] = Some(root);
+
+        let intersect = |idom: &[Option<usize>], mut a: usize, mut b: usize| {
+            while a != b {
+                while rank[a] > rank[b] {
+                    a = idom[a].unwrap();
+                }
+                while rank[b] > rank[a] {
+                    b = idom[b].unwrap();
+                }
+            }
+            a
+        };
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+
+            for &u in &order[1..] {
+                let new_idom = predecessors.edges[u]
+                    .iter()
+                    .filter(|&&p| idom[p].is_some())
+                    .fold(None, |acc, &p| match acc {
+                        None => Some(p),
+                        Some(q) => Some(intersect(&idom, p, q)),
+                    });
+
+                if new_idom != idom[u] {
+                    idom[u] = new_idom;
+                    changed = true;
+                }
+            }
+        }
+
+        idom[root] = None;
+        idom
+    }
+
+    /// Nodes reachable from `from` in DFS postorder.
+    fn postorder(&self, from: usize) -> Vec<usize> {
+        let mut seen = vec![false; self.len()];
+        let mut order = Vec::new();
+        let mut stack = vec![(from, 0)];
+        seen[from] = true;
+
+        while let Some(&mut (u, ref mut next)) = stack.last_mut() {
+            match self.edges[u].get(*next) {
+                Some(&v) => {
+                    *next += 1;
+                    if !seen[v] {
+                        seen[v] = true;
+                        stack.push((v, 0));
+                    }
+                }
+                None => {
+                    order.push(u);
+                    stack.pop();
+                }
+            }
+        }
+
+        order
+    }
+}
+
+/// Strongly connected components of a [`Graph`], and the DAG between them.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Condensation {
+    /// Nodes of every component, in topological order: edges only go from a
+    /// component to a later one.
+    pub components: Vec<Vec<usize>>,
+    /// Index in `components` of the component of every node.
+    pub component_of: Vec<usize>,
+    /// Edges between components, without duplicates.
+    pub edges: Vec<Vec<usize>>,
+    /// Whether a component contains a cycle, either because it has more
+    /// than one node or a node with an edge to itself.
+    pub cyclic: Vec<bool>,
+}
+
+/// Result of [`Graph::count_paths`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathCount {
+    Finite(u64),
+    /// Some walk can loop around a cycle as many times as it wants.
+    Infinite,
+}
+
+impl PathCount {
+    pub fn finite(self) -> Option<u64> {
+        match self {
+            PathCount::Finite(n) => Some(n),
+            PathCount::Infinite => None,
+        }
+    }
+}
+
+/// Panics if the sum of two finite counts overflows `u64`.
+impl Add for PathCount {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        match (self, rhs) {
+            (PathCount::Finite(a), PathCount::Finite(b)) => {
+                PathCount::Finite(a.checked_add(b).expect("Path count overflows u64"))
+            }
+            _ => PathCount::Infinite,
+        }
+    }
+}
+
+impl Sum for PathCount {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(PathCount::Finite(0), Add::add)
+    }
+}
+
+impl Display for PathCount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathCount::Finite(n) => write!(f, "{n}"),
+            PathCount::Infinite => write!(f, "infinite"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::graph::{Graph, ParseGraphError, PathCount};
+
+    const DIAMONDS: &str = "\
+a: b c
+b: d
+c: d
+d: e f
+e: g
+f: g";
+
+    #[test]
+    fn parse() {
+        let graph: Graph = DIAMONDS.parse().unwrap();
+        assert_eq!(graph.len(), 7);
+
+        let d = graph.id("d").unwrap();
+        let names: Vec<&str> = graph.neighbors(d).iter().map(|&v| graph.name(v)).collect();
+        assert_eq!(names, ["e", "f"]);
+        assert!(graph.neighbors(graph.id("g").unwrap()).is_empty());
+
+        assert_eq!(
+            "a: b\nb c\n".parse::<Graph>().unwrap_err(),
+            ParseGraphError { line: 2 }
+        );
+    }
+
+    #[test]
+    fn ordering_and_cycles() {
+        let graph: Graph = DIAMONDS.parse().unwrap();
+        let order = graph.topological_sort().unwrap();
+        let mut position = vec![0; graph.len()];
+        for (i, &u) in order.iter().enumerate() {
+            position[u] = i;
+        }
+        assert!((0..graph.len()).all(|u| {
+            graph
+                .neighbors(u)
+                .iter()
+                .all(|&v| position[u] < position[v])
+        }));
+        assert_eq!(graph.find_cycle(), None);
+
+        let cyclic: Graph = "x: a\na: b\nb: c\nc: a d".parse().unwrap();
+        assert_eq!(cyclic.topological_sort(), None);
+        let cycle: Vec<&str> = cyclic
+            .find_cycle()
+            .unwrap()
+            .into_iter()
+            .map(|u| cyclic.name(u))
+            .collect();
+        assert_eq!(cycle, ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn paths() {
+        let graph: Graph = DIAMONDS.parse().unwrap();
+        let id = |name| graph.id(name).unwrap();
+
+        assert_eq!(graph.count_paths(id("a"), id("g")), PathCount::Finite(4));
+        assert_eq!(graph.count_paths(id("b"), id("f")), PathCount::Finite(1));
+        assert_eq!(graph.count_paths(id("g"), id("a")), PathCount::Finite(0));
+        assert_eq!(
+            graph.count_paths_through(id("a"), id("g"), &[id("c"), id("f")]),
+            PathCount::Finite(1)
+        );
+        assert_eq!(
+            graph.count_paths_through(id("a"), id("g"), &[id("d")]),
+            PathCount::Finite(4)
+        );
+        assert_eq!(
+            graph.count_paths_through(id("a"), id("g"), &[id("e"), id("f")]),
+            PathCount::Finite(0)
+        );
+
+        let reachable = graph.reachable(id("e"));
+        assert_eq!(reachable.iter().filter(|&&r| r).count(), 2);
+        assert!(reachable[id("g")]);
+    }
+
+    #[test]
+    fn dominators() {
+        let graph: Graph = "x: y\na: b c\nb: d\nc: d\nd: e f\ne: g\nf: g"
+            .parse()
+            .unwrap();
+        let id = |name| graph.id(name).unwrap();
+        let idom = graph.dominators(id("a"));
+
+        assert_eq!(idom[id("a")], None);
+        assert_eq!(idom[id("b")], Some(id("a")));
+        assert_eq!(idom[id("d")], Some(id("a")));
+        assert_eq!(idom[id("f")], Some(id("d")));
+        assert_eq!(idom[id("g")], Some(id("d")));
+        assert_eq!(idom[id("y")], None);
+    }
+
+    #[test]
+    fn components() {
+        let graph: Graph = "a: b\nb: c e\nc: a d\nd: d\ne: f\nf: e g".parse().unwrap();
+        let id = |name| graph.id(name).unwrap();
+        let condensation = graph.condensation();
+
+        let mut names: Vec<Vec<&str>> = condensation
+            .components
+            .iter()
+            .map(|nodes| {
+                let mut names: Vec<&str> = nodes.iter().map(|&u| graph.name(u)).collect();
+                names.sort_unstable();
+                names
+            })
+            .collect();
+        assert_eq!(names.remove(0), ["a", "b", "c"]);
+        names.sort();
+        assert_eq!(names, [vec!["d"], vec!["e", "f"], vec!["g"]]);
+
+        let c = |name| condensation.component_of[id(name)];
+        assert_eq!(condensation.edges[c("a")], {
+            let mut edges = vec![c("d"), c("e")];
+            edges.sort_unstable();
+            edges
+        });
+        assert!(condensation.cyclic[c("d")]);
+        assert!(!condensation.cyclic[c("g")]);
+    }
+
+    #[test]
+    fn infinite_paths() {
+        let graph: Graph = "s: a x\na: b\nb: a t\nx: t y\ny: y".parse().unwrap();
+        let id = |name| graph.id(name).unwrap();
+
+        assert_eq!(graph.count_paths(id("s"), id("t")), PathCount::Infinite);
+        assert_eq!(graph.count_paths(id("x"), id("t")), PathCount::Finite(1));
+        // `y` loops, but never reaches `t`
+        assert_eq!(graph.count_paths(id("y"), id("t")), PathCount::Finite(0));
+        assert_eq!(
+            graph.count_paths_through(id("s"), id("t"), &[id("x")]),
+            PathCount::Finite(1)
+        );
+        assert_eq!(
+            graph.count_paths_through(id("s"), id("t"), &[id("b")]),
+            PathCount::Infinite
+        );
+        assert_eq!(
+            graph.count_paths_through(id("s"), id("t"), &[id("b"), id("x")]),
+            PathCount::Finite(0)
+        );
+
+        let graph: Graph = "s: t\nt: s".parse().unwrap();
+        assert_eq!(graph.count_paths(0, 1), PathCount::Infinite);
+        assert_eq!(PathCount::Finite(3).to_string(), "3");
+        assert_eq!(PathCount::Infinite.finite(), None);
+        assert_eq!(
+            PathCount::Finite(u64::MAX) + PathCount::Infinite,
+            PathCount::Infinite
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Path count overflows u64")]
+    fn path_count_overflow() {
+        let _ = PathCount::Finite(u64::MAX) + PathCount::Finite(1);
+    }
+}
@@ -0,0 +1,563 @@
+use std::{
+    convert::Infallible,
+    error::Error,
+    fmt::{Debug, Display},
+    ops::{Index, IndexMut},
+};
+
+use smallvec::SmallVec;
+
+use crate::util::point::Point;
+
+mod automaton;
+mod components;
+mod display;
+mod path;
+mod transform;
+
+pub use automaton::Cycle;
+pub use components::{Components, Region, Regions};
+pub use display::{Color, Render, Style};
+pub use path::SearchResult;
+
+/// Which neighbors of a cell are considered adjacent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Up, down, left and right.
+    Cardinal,
+    /// Cardinals and diagonals.
+    All,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid2D<T> {
+    data: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+/// Error returned when parsing a [`Grid2D`] from text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseGridError<E> {
+    /// Row `row` (0-based, displayed as a 1-based line number) doesn't have
+    /// as many cells as the first one.
+    Ragged {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// The cell mapper rejected the character at `(x, y)`.
+    Cell { x: usize, y: usize, error: E },
+}
+
+impl<E: Display> Display for ParseGridError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseGridError::Ragged {
+                row,
+                expected,
+                found,
+            } => write!(f, "Line {} has {found} cells, expected {expected}", row + 1),
+            ParseGridError::Cell { x, y, error } => {
+                write!(f, "Invalid cell at ({x}, {y}): {error}")
+            }
+        }
+    }
+}
+
+impl<E: Debug + Display> Error for ParseGridError<E> {}
+
+impl<T: From<char>> From<&str> for Grid2D<T> {
+    fn from(input: &str) -> Self {
+        Self::parse_with(input, |_, _, ch| Ok::<_, Infallible>(T::from(ch)))
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl Grid2D<u8> {
+    /// Byte grid taken straight from `as_bytes()`, for ASCII inputs.
+    pub fn from_bytes(input: &str) -> Result<Self, ParseGridError<Infallible>> {
+        let width = input.lines().next().map_or(0, str::len);
+
+        let mut data = Vec::with_capacity(input.len());
+        let mut height = 0;
+
+        for (y, line) in input.lines().enumerate() {
+            if line.len() != width {
+                return Err(ParseGridError::Ragged {
+                    row: y,
+                    expected: width,
+                    found: line.len(),
+                });
+            }
+            data.extend_from_slice(line.as_bytes());
+            height += 1;
+        }
+
+        Ok(Self {
+            data,
+            width,
+            height,
+        })
+    }
+
+    /// Like [`Grid2D::from_bytes`], padding rows shorter than the longest one with `fill`.
+    pub fn from_bytes_padded(input: &str, fill: u8) -> Self {
+        let width = input.lines().map(str::len).max().unwrap_or(0);
+
+        let mut data = Vec::with_capacity(input.len());
+        let mut height = 0;
+
+        for line in input.lines() {
+            data.extend_from_slice(line.as_bytes());
+            data.resize(data.len() + width - line.len(), fill);
+            height += 1;
+        }
+
+        Self {
+            data,
+            width,
+            height,
+        }
+    }
+}
+
+impl<T> Grid2D<T> {
+    /// Parses one cell per character, calling `f(x, y, ch)` for each of them.
+    /// Every row must have as many characters as the first one.
+    pub fn parse_with<E, F>(input: &str, mut f: F) -> Result<Self, ParseGridError<E>>
+    where
+        F: FnMut(usize, usize, char) -> Result<T, E>,
+    {
+        let width = input.lines().next().map_or(0, |line| line.chars().count());
+
+        let mut data = Vec::with_capacity(input.len());
+        let mut height = 0;
+
+        for (y, line) in input.lines().enumerate() {
+            let start = data.len();
+
+            for (x, ch) in line.chars().enumerate() {
+                data.push(f(x, y, ch).map_err(|error| ParseGridError::Cell { x, y, error })?);
+            }
+
+            if data.len() - start != width {
+                return Err(ParseGridError::Ragged {
+                    row: y,
+                    expected: width,
+                    found: data.len() - start,
+                });
+            }
+            height += 1;
+        }
+
+        Ok(Self {
+            data,
+            width,
+            height,
+        })
+    }
+
+    /// Like [`Grid2D::parse_with`], but rows shorter than the longest one are
+    /// padded with `fill` instead of being rejected.
+    pub fn parse_padded_with<E, F>(
+        input: &str,
+        fill: T,
+        mut f: F,
+    ) -> Result<Self, ParseGridError<E>>
+    where
+        T: Clone,
+        F: FnMut(usize, usize, char) -> Result<T, E>,
+    {
+        let width = input
+            .lines()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let mut data = Vec::with_capacity(input.len());
+        let mut height = 0;
+
+        for (y, line) in input.lines().enumerate() {
+            let start = data.len();
+
+            for (x, ch) in line.chars().enumerate() {
+                data.push(f(x, y, ch).map_err(|error| ParseGridError::Cell { x, y, error })?);
+            }
+
+            data.resize(start + width, fill.clone());
+            height += 1;
+        }
+
+        Ok(Self {
+            data,
+            width,
+            height,
+        })
+    }
+
+    /// Builds a grid by calling `f(x, y)` for every cell, row by row.
+    pub fn from_fn<F: FnMut(usize, usize) -> T>(width: usize, height: usize, mut f: F) -> Self {
+        let mut data = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                data.push(f(x, y));
+            }
+        }
+
+        Self {
+            data,
+            width,
+            height,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    /// Panics if `y` is out of bounds.
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "Row {y} out of bounds");
+        &self.data[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Panics if `y` is out of bounds.
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        assert!(y < self.height, "Row {y} out of bounds");
+        &mut self.data[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Cells of column `x` from top to bottom. Panics if `x` is out of bounds.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "Column {x} out of bounds");
+        self.data[x..].iter().step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Position of the first cell, row by row, for which `pred` holds.
+    pub fn find<F: FnMut(&T) -> bool>(&self, pred: F) -> Option<(usize, usize)> {
+        let idx = self.data.iter().position(pred)?;
+        Some((idx % self.width, idx / self.width))
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid2D<U> {
+        Grid2D {
+            data: self.data.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = ((usize, usize), &mut T)> {
+        let width = self.width.max(1);
+        self.data
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, cell)| ((i % width, i / width), cell))
+    }
+}
+
+impl<T: Clone> Grid2D<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self {
+            data: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid2D<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get(x, y).unwrap_or_else(|| {
+            panic!(
+                "({x}, {y}) out of bounds of a {}x{} grid",
+                self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid2D<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(x, y)
+            .unwrap_or_else(|| panic!("({x}, {y}) out of bounds of a {width}x{height} grid"))
+    }
+}
+
+impl<T> Grid2D<T> {
+    fn index(&self, x: usize, y: usize) -> usize {
+        y * self.width + x
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x < self.width && y < self.height {
+            Some(&self.data[self.index(x, y)])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            let idx = self.index(x, y);
+            Some(&mut self.data[idx])
+        } else {
+            None
+        }
+    }
+
+    pub fn in_bounds(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn all_neighbors(&self, x: usize, y: usize) -> SmallVec<[((usize, usize), &T); 8]> {
+        const NEIGHBORS: [(isize, isize); 8] = [
+            (-1, -1),
+            (-1, 0),
+            (-1, 1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+            (0, 1),
+            (0, -1),
+        ];
+
+        NEIGHBORS
+            .iter()
+            .filter_map(|&(dx, dy)| {
+                let nx = x.checked_add_signed(dx)?;
+                let ny = y.checked_add_signed(dy)?;
+                Some(((nx, ny), self.get(nx, ny)?))
+            })
+            .collect()
+    }
+
+    pub fn cardinal_neighbors(&self, x: usize, y: usize) -> SmallVec<[((usize, usize), &T); 4]> {
+        const CARDINALS: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+        CARDINALS
+            .iter()
+            .filter_map(|&(dx, dy)| {
+                let nx = x.checked_add_signed(dx)?;
+                let ny = y.checked_add_signed(dy)?;
+                Some(((nx, ny), self.get(nx, ny)?))
+            })
+            .collect()
+    }
+
+    pub fn neighbors(
+        &self,
+        x: usize,
+        y: usize,
+        connectivity: Connectivity,
+    ) -> SmallVec<[((usize, usize), &T); 8]> {
+        match connectivity {
+            Connectivity::Cardinal => self.cardinal_neighbors(x, y).into_iter().collect(),
+            Connectivity::All => self.all_neighbors(x, y),
+        }
+    }
+
+    /// Cell at a signed position, `None` outside the grid.
+    pub fn get_point(&self, p: Point) -> Option<&T> {
+        let (x, y) = p.to_usize()?;
+        self.get(x, y)
+    }
+
+    /// Cell at a signed position on a grid that repeats in every direction.
+    /// Panics if the grid is empty.
+    pub fn wrapping_get(&self, p: Point) -> &T {
+        assert!(!self.data.is_empty(), "Wrapping lookup on an empty grid");
+        let x = p.x.rem_euclid(self.width as i64) as usize;
+        let y = p.y.rem_euclid(self.height as i64) as usize;
+        &self.data[self.index(x, y)]
+    }
+
+    /// Like [`Grid2D::neighbors`], but the grid wraps around at the edges.
+    /// Panics if the grid is empty.
+    pub fn wrapping_neighbors(
+        &self,
+        x: usize,
+        y: usize,
+        connectivity: Connectivity,
+    ) -> SmallVec<[((usize, usize), &T); 8]> {
+        assert!(!self.data.is_empty(), "Wrapping lookup on an empty grid");
+        let p = Point::from((x, y));
+        let neighbors: SmallVec<[Point; 8]> = match connectivity {
+            Connectivity::Cardinal => p.cardinal_neighbors().into_iter().collect(),
+            Connectivity::All => p.all_neighbors().into_iter().collect(),
+        };
+
+        neighbors
+            .into_iter()
+            .map(|n| {
+                let nx = n.x.rem_euclid(self.width as i64) as usize;
+                let ny = n.y.rem_euclid(self.height as i64) as usize;
+                ((nx, ny), &self.data[self.index(nx, ny)])
+            })
+            .collect()
+    }
+
+    pub fn iter(&self) -> Grid2DIter<'_, T> {
+        Grid2DIter {
+            grid: self,
+            x: 0,
+            y: 0,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Grid2DIter<'a, T> {
+    grid: &'a Grid2D<T>,
+    x: usize,
+    y: usize,
+}
+
+impl<'a, T> Iterator for Grid2DIter<'a, T> {
+    type Item = ((usize, usize), &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let idx = self.y * self.grid.width + self.x;
+        if idx >= self.grid.data.len() {
+            return None;
+        }
+
+        let item = &self.grid.data[idx];
+        let coords = (self.x, self.y);
+
+        self.x += 1;
+        if self.x >= self.grid.width {
+            self.x = 0;
+            self.y += 1;
+        }
+
+        Some((coords, item))
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Grid2D<T> {
+    type Item = ((usize, usize), &'a T);
+    type IntoIter = Grid2DIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::{
+        grid2d::{Connectivity, Grid2D, ParseGridError},
+        point::Point,
+    };
+
+    #[test]
+    fn parse_with() {
+        let grid = Grid2D::parse_with("12\n34", |_, _, ch| ch.to_digit(10).ok_or(ch)).unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert_eq!(grid.get(1, 1), Some(&4));
+
+        assert_eq!(
+            Grid2D::parse_with("12\n3x", |_, _, ch| ch.to_digit(10).ok_or(ch)).unwrap_err(),
+            ParseGridError::Cell {
+                x: 1,
+                y: 1,
+                error: 'x'
+            }
+        );
+    }
+
+    #[test]
+    fn ragged() {
+        let error = Grid2D::<u8>::from_bytes("...\n..\n...").unwrap_err();
+        assert_eq!(
+            error,
+            ParseGridError::Ragged {
+                row: 1,
+                expected: 3,
+                found: 2
+            }
+        );
+        assert_eq!(error.to_string(), "Line 2 has 2 cells, expected 3");
+
+        let grid = Grid2D::from_bytes_padded("...\n.#\n", b' ');
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [b"...", b".# "]);
+    }
+
+    #[test]
+    fn zero_width_rows() {
+        let grid = Grid2D::<u8>::new(0, 3, 0);
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [&[] as &[u8]; 3]);
+    }
+
+    #[test]
+    fn width_counts_chars() {
+        let grid: Grid2D<char> = Grid2D::from("é.\n.é");
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert_eq!(grid.get(1, 1), Some(&'é'));
+    }
+
+    #[test]
+    fn wrapping() {
+        let grid = Grid2D::from_bytes("abc\ndef").unwrap();
+
+        assert_eq!(grid.get_point(Point::new(-1, 0)), None);
+        assert_eq!(grid.get_point(Point::new(2, 1)), Some(&b'f'));
+        assert_eq!(grid.wrapping_get(Point::new(-1, 0)), &b'c');
+        assert_eq!(grid.wrapping_get(Point::new(4, -3)), &b'e');
+
+        let neighbors: Vec<u8> = grid
+            .wrapping_neighbors(0, 0, Connectivity::Cardinal)
+            .into_iter()
+            .map(|(_, &c)| c)
+            .collect();
+        assert_eq!(neighbors, b"dbdc");
+    }
+
+    #[test]
+    #[should_panic(expected = "Wrapping lookup on an empty grid")]
+    fn wrapping_empty() {
+        Grid2D::<u8>::new(0, 3, 0).wrapping_get(Point::new(1, 1));
+    }
+
+    #[test]
+    fn views() {
+        let mut grid = Grid2D::new(3, 2, 0);
+        grid[(1, 0)] = 5;
+        grid.row_mut(1)[2] = 7;
+        grid.iter_mut().for_each(|((x, _), cell)| *cell += x);
+
+        assert_eq!(grid.row(0), &[0, 6, 2]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), vec![2, 9]);
+        assert_eq!(
+            grid.columns().map(|c| c.sum::<usize>()).collect::<Vec<_>>(),
+            vec![0, 7, 11]
+        );
+        assert_eq!(grid.find(|&c| c > 6), Some((2, 1)));
+        assert!(grid.map(|&c| c % 2 == 1)[(2, 1)]);
+        assert_eq!(grid[(0, 1)], 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "(3, 0) out of bounds of a 3x2 grid")]
+    fn index_out_of_bounds() {
+        let grid = Grid2D::new(3, 2, 0);
+        let _ = grid[(3, 0)];
+    }
+}
@@ -0,0 +1,209 @@
+use std::hash::Hash;
+
+use rustc_hash::FxHashMap;
+use smallvec::SmallVec;
+
+use crate::util::grid2d::{Connectivity, Grid2D};
+
+/// States of an automaton from its initial grid until it first repeats.
+#[derive(Debug, Clone)]
+pub struct Cycle<T> {
+    /// Step at which the periodic part starts.
+    pub start: usize,
+    /// Number of steps after which the states repeat, 1 for a fixed point.
+    pub period: usize,
+    states: Vec<Grid2D<T>>,
+}
+
+impl<T> Cycle<T> {
+    /// State after `n` steps, however large `n` is.
+    pub fn state_at(&self, n: usize) -> &Grid2D<T> {
+        if n < self.start {
+            &self.states[n]
+        } else {
+            &self.states[self.start + (n - self.start) % self.period]
+        }
+    }
+}
+
+impl<T> Grid2D<T> {
+    fn apply_rule<F>(&self, x: usize, y: usize, connectivity: Connectivity, rule: &mut F) -> T
+    where
+        F: FnMut(&T, &[&T]) -> T,
+    {
+        let neighbors: SmallVec<[&T; 8]> = self
+            .neighbors(x, y, connectivity)
+            .into_iter()
+            .map(|(_, cell)| cell)
+            .collect();
+
+        rule(&self.data[self.index(x, y)], &neighbors)
+    }
+
+    /// Next generation of the grid, computing every cell at once with
+    /// `rule(cell, neighbors)`. Cells outside the grid are not neighbors.
+    pub fn step<F>(&self, connectivity: Connectivity, mut rule: F) -> Self
+    where
+        F: FnMut(&T, &[&T]) -> T,
+    {
+        Grid2D::from_fn(self.width, self.height, |x, y| {
+            self.apply_rule(x, y, connectivity, &mut rule)
+        })
+    }
+}
+
+impl<T: PartialEq> Grid2D<T> {
+    /// Steps until the grid stops changing. Returns the stable grid and the
+    /// number of steps that changed something.
+    pub fn run_until_stable<F>(&self, connectivity: Connectivity, mut rule: F) -> (Self, usize)
+    where
+        T: Clone,
+        F: FnMut(&T, &[&T]) -> T,
+    {
+        let mut grid = self.clone();
+        let mut steps = 0;
+
+        loop {
+            let next = grid.step(connectivity, &mut rule);
+            if next == grid {
+                return (grid, steps);
+            }
+            grid = next;
+            steps += 1;
+        }
+    }
+
+    /// Same result as [`Grid2D::run_until_stable`], but after the first step
+    /// only cells next to a change are evaluated again. `rule` must only
+    /// depend on its arguments.
+    pub fn run_until_stable_incremental<F>(
+        &self,
+        connectivity: Connectivity,
+        mut rule: F,
+    ) -> (Self, usize)
+    where
+        T: Clone,
+        F: FnMut(&T, &[&T]) -> T,
+    {
+        let mut grid = self.clone();
+        let mut worklist: Vec<(usize, usize)> = (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .collect();
+        // Step at which each cell was last queued, so it's only queued once
+        let mut queued = vec![0; self.data.len()];
+        let mut steps = 0;
+
+        loop {
+            let changes: Vec<((usize, usize), T)> = worklist
+                .drain(..)
+                .filter_map(|(x, y)| {
+                    let next = grid.apply_rule(x, y, connectivity, &mut rule);
+                    (next != grid.data[grid.index(x, y)]).then_some(((x, y), next))
+                })
+                .collect();
+
+            if changes.is_empty() {
+                return (grid, steps);
+            }
+            steps += 1;
+
+            for ((x, y), value) in changes {
+                let idx = grid.index(x, y);
+                grid.data[idx] = value;
+
+                let neighbors = grid.neighbors(x, y, connectivity);
+                for pos in neighbors.iter().map(|&(pos, _)| pos).chain([(x, y)]) {
+                    let idx = grid.index(pos.0, pos.1);
+                    if queued[idx] != steps {
+                        queued[idx] = steps;
+                        worklist.push(pos);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<T: Clone + Hash + Eq> Grid2D<T> {
+    /// Steps until a state comes back. The state space of a finite grid is
+    /// finite so this always ends, but only quickly if the cycle is short.
+    pub fn find_cycle<F>(&self, connectivity: Connectivity, mut rule: F) -> Cycle<T>
+    where
+        F: FnMut(&T, &[&T]) -> T,
+    {
+        let mut seen = FxHashMap::default();
+        let mut states = vec![self.clone()];
+
+        loop {
+            let current = states.last().unwrap();
+            if let Some(&start) = seen.get(current) {
+                let period = states.len() - 1 - start;
+                states.pop();
+                return Cycle {
+                    start,
+                    period,
+                    states,
+                };
+            }
+            seen.insert(current.clone(), states.len() - 1);
+
+            let next = current.step(connectivity, &mut rule);
+            states.push(next);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::grid2d::{Connectivity, Grid2D};
+
+    fn life(&cell: &bool, neighbors: &[&bool]) -> bool {
+        let alive = neighbors.iter().filter(|&&&n| n).count();
+        alive == 3 || (cell && alive == 2)
+    }
+
+    fn parse(input: &str) -> Grid2D<bool> {
+        Grid2D::from_bytes(input).unwrap().map(|&c| c == b'#')
+    }
+
+    #[test]
+    fn step() {
+        let blinker = parse(".....\n..#..\n..#..\n..#..\n.....");
+
+        let next = blinker.step(Connectivity::All, life);
+        assert_eq!(next, parse(".....\n.....\n.###.\n.....\n....."));
+        assert_eq!(next.step(Connectivity::All, life), blinker);
+    }
+
+    #[test]
+    fn cycles() {
+        let blinker = parse(".....\n..#..\n..#..\n..#..\n.....");
+        let cycle = blinker.find_cycle(Connectivity::All, life);
+        assert_eq!((cycle.start, cycle.period), (0, 2));
+        assert_eq!(cycle.state_at(1_000_000_001), cycle.state_at(1));
+
+        // Three cells in an L become a block after one step
+        let block = parse("....\n.##.\n..#.\n....");
+        let cycle = block.find_cycle(Connectivity::All, life);
+        assert_eq!((cycle.start, cycle.period), (1, 1));
+        assert_eq!(cycle.state_at(50), &parse("....\n.##.\n.##.\n...."));
+    }
+
+    #[test]
+    fn stable() {
+        // Erode: a cell survives while at least 3 of its 4 neighbors do
+        let erode = |&cell: &bool, neighbors: &[&bool]| {
+            cell && neighbors.iter().filter(|&&&n| n).count() >= 3
+        };
+        let grid = parse("######\n######\n###...\n######\n#.####");
+
+        let (full, full_steps) = grid.run_until_stable(Connectivity::Cardinal, erode);
+        let (incremental, incremental_steps) =
+            grid.run_until_stable_incremental(Connectivity::Cardinal, erode);
+
+        assert_eq!(full, incremental);
+        assert_eq!(full_steps, incremental_steps);
+        assert!(full_steps > 1);
+        assert_eq!(full.step(Connectivity::Cardinal, erode), full);
+    }
+}
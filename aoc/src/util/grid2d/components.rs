@@ -0,0 +1,291 @@
+use std::collections::VecDeque;
+
+use crate::util::grid2d::{Connectivity, Grid2D};
+
+type Pos = (usize, usize);
+
+/// Measurements of one connected region of a grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Region {
+    /// Number of cells.
+    pub size: usize,
+    /// Top-left corner of the bounding box.
+    pub min: Pos,
+    /// Bottom-right corner of the bounding box, inclusive.
+    pub max: Pos,
+    /// Number of cell edges between the region and anything else,
+    /// grid border included.
+    pub perimeter: usize,
+    /// Number of straight sides of the region's outline, holes included.
+    pub sides: usize,
+}
+
+/// Connected-component labelling of a grid.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Components {
+    /// Index into `regions` of every cell, `None` for cells that didn't
+    /// match the predicate.
+    pub labels: Grid2D<Option<usize>>,
+    pub regions: Vec<Region>,
+}
+
+impl Components {
+    pub fn label(&self, (x, y): Pos) -> Option<usize> {
+        self.labels.get(x, y).copied().flatten()
+    }
+
+    pub fn region_at(&self, pos: Pos) -> Option<&Region> {
+        self.label(pos).map(|label| &self.regions[label])
+    }
+}
+
+impl<T> Grid2D<T> {
+    /// Cells reachable from `start` through cardinal steps on cells matching
+    /// `pred`, in BFS order. Empty if `start` itself doesn't match.
+    pub fn flood_fill<F>(&self, start: Pos, mut pred: F) -> Vec<Pos>
+    where
+        F: FnMut(Pos, &T) -> bool,
+    {
+        match self.get(start.0, start.1) {
+            Some(cell) if pred(start, cell) => {}
+            _ => return Vec::new(),
+        }
+
+        let mut seen = vec![false; self.data.len()];
+        seen[self.index(start.0, start.1)] = true;
+        self.fill_from(start, Connectivity::Cardinal, &mut pred, &mut seen)
+    }
+
+    /// Splits the cells matching `pred` into connected regions.
+    pub fn components<F>(&self, pred: F, connectivity: Connectivity) -> Components
+    where
+        F: FnMut(Pos, &T) -> bool,
+    {
+        let mut labels = Grid2D::new(self.width, self.height, None);
+        let mut cells = Vec::new();
+
+        for region in self.regions(pred, connectivity) {
+            let label = cells.len();
+            for &(x, y) in &region {
+                labels.data[self.index(x, y)] = Some(label);
+            }
+            cells.push(region);
+        }
+
+        let regions = cells
+            .iter()
+            .enumerate()
+            .map(|(label, cells)| measure(&labels, label, cells))
+            .collect();
+
+        Components { labels, regions }
+    }
+
+    /// Cells of each connected region matching `pred`, found one at a time
+    /// in row-major order of their first cell, so a caller can stop early.
+    pub fn regions<F>(&self, pred: F, connectivity: Connectivity) -> Regions<'_, T, F>
+    where
+        F: FnMut(Pos, &T) -> bool,
+    {
+        Regions {
+            grid: self,
+            pred,
+            connectivity,
+            seen: vec![false; self.data.len()],
+            next: 0,
+        }
+    }
+
+    /// BFS from an already matched and seen `start`.
+    fn fill_from<F>(
+        &self,
+        start: Pos,
+        connectivity: Connectivity,
+        pred: &mut F,
+        seen: &mut [bool],
+    ) -> Vec<Pos>
+    where
+        F: FnMut(Pos, &T) -> bool,
+    {
+        let mut cells = Vec::new();
+        let mut queue = VecDeque::from([start]);
+
+        while let Some((x, y)) = queue.pop_front() {
+            cells.push((x, y));
+
+            for (n, cell) in self.neighbors(x, y, connectivity) {
+                let idx = self.index(n.0, n.1);
+                if seen[idx] || !pred(n, cell) {
+                    continue;
+                }
+                seen[idx] = true;
+                queue.push_back(n);
+            }
+        }
+
+        cells
+    }
+}
+
+/// Iterator returned by [`Grid2D::regions`].
+pub struct Regions<'a, T, F> {
+    grid: &'a Grid2D<T>,
+    pred: F,
+    connectivity: Connectivity,
+    seen: Vec<bool>,
+    next: usize,
+}
+
+impl<T, F> Iterator for Regions<'_, T, F>
+where
+    F: FnMut(Pos, &T) -> bool,
+{
+    type Item = Vec<Pos>;
+
+    fn next(&mut self) -> Option<Vec<Pos>> {
+        let grid = self.grid;
+
+        while self.next < grid.data.len() {
+            let idx = self.next;
+            self.next += 1;
+            if self.seen[idx] {
+                continue;
+            }
+            self.seen[idx] = true;
+
+            let pos = (idx % grid.width, idx / grid.width);
+            if !(self.pred)(pos, &grid.data[idx]) {
+                continue;
+            }
+
+            return Some(grid.fill_from(pos, self.connectivity, &mut self.pred, &mut self.seen));
+        }
+
+        None
+    }
+}
+
+fn measure(labels: &Grid2D<Option<usize>>, label: usize, cells: &[Pos]) -> Region {
+    let same = |x: usize, y: usize, dx: isize, dy: isize| {
+        let (Some(nx), Some(ny)) = (x.checked_add_signed(dx), y.checked_add_signed(dy)) else {
+            return false;
+        };
+        labels.get(nx, ny) == Some(&Some(label))
+    };
+
+    let mut region = Region {
+        size: cells.len(),
+        min: (usize::MAX, usize::MAX),
+        max: (0, 0),
+        perimeter: 0,
+        sides: 0,
+    };
+
+    for &(x, y) in cells {
+        region.min = (region.min.0.min(x), region.min.1.min(y));
+        region.max = (region.max.0.max(x), region.max.1.max(y));
+
+        region.perimeter += [(-1, 0), (1, 0), (0, -1), (0, 1)]
+            .into_iter()
+            .filter(|&(dx, dy)| !same(x, y, dx, dy))
+            .count();
+
+        // A polygon has as many sides as corners: a cell corner is convex
+        // when both orthogonal neighbours are outside, concave when both are
+        // inside but the diagonal isn't
+        for (dx, dy) in [(-1, -1), (1, -1), (-1, 1), (1, 1)] {
+            let horizontal = same(x, y, dx, 0);
+            let vertical = same(x, y, 0, dy);
+
+            if (!horizontal && !vertical) || (horizontal && vertical && !same(x, y, dx, dy)) {
+                region.sides += 1;
+            }
+        }
+    }
+
+    region
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::grid2d::{Connectivity, Grid2D, components::Region};
+
+    #[test]
+    fn flood_fill() {
+        let grid = Grid2D::from_bytes("..#\n.#.\n#..").unwrap();
+
+        let mut filled = grid.flood_fill((0, 0), |_, &c| c == b'.');
+        filled.sort();
+        assert_eq!(filled, vec![(0, 0), (0, 1), (1, 0)]);
+
+        assert!(grid.flood_fill((2, 0), |_, &c| c == b'.').is_empty());
+    }
+
+    #[test]
+    fn regions() {
+        let grid = Grid2D::from_bytes("AAAA\nBBCD\nBBCC\nEEEC").unwrap();
+        let components = grid.components(|_, &c| c != b'C', Connectivity::Cardinal);
+        let sizes: Vec<usize> = components.regions.iter().map(|r| r.size).collect();
+        assert_eq!(sizes, vec![12]);
+
+        let components = grid.components(|_, &c| c == b'C', Connectivity::Cardinal);
+        assert_eq!(
+            components.regions,
+            vec![Region {
+                size: 4,
+                min: (2, 1),
+                max: (3, 3),
+                perimeter: 10,
+                sides: 8,
+            }]
+        );
+        assert_eq!(components.label((0, 0)), None);
+        assert_eq!(components.label((3, 3)), Some(0));
+    }
+
+    #[test]
+    fn lazy_regions() {
+        let grid = Grid2D::from_bytes("A.A\n.A.\nAA.").unwrap();
+
+        let mut checked = 0;
+        let first = grid
+            .regions(
+                |_, &c| {
+                    checked += 1;
+                    c == b'A'
+                },
+                Connectivity::Cardinal,
+            )
+            .next();
+        assert_eq!(first, Some(vec![(0, 0)]));
+        assert_eq!(checked, 3);
+
+        let sizes: Vec<usize> = grid
+            .regions(|_, &c| c == b'A', Connectivity::Cardinal)
+            .map(|cells| cells.len())
+            .collect();
+        assert_eq!(sizes, vec![1, 1, 3]);
+    }
+
+    #[test]
+    fn holes_and_diagonals() {
+        let grid = Grid2D::from_bytes("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO").unwrap();
+
+        let outer = grid.components(|_, &c| c == b'O', Connectivity::Cardinal);
+        assert_eq!(outer.regions.len(), 1);
+        assert_eq!(outer.regions[0].perimeter, 36);
+        assert_eq!(outer.regions[0].sides, 20);
+
+        let cardinal = grid.components(|_, &c| c == b'X', Connectivity::Cardinal);
+        let all = grid.components(|_, &c| c == b'X', Connectivity::All);
+        assert_eq!(cardinal.regions.len(), 4);
+        assert_eq!(all.regions.len(), 4);
+
+        let diagonal = Grid2D::from_bytes("X.\n.X").unwrap();
+        let all = diagonal.components(|_, &c| c == b'X', Connectivity::All);
+        assert_eq!(all.regions.len(), 1);
+        assert_eq!(all.regions[0].sides, 8);
+    }
+}
@@ -0,0 +1,163 @@
+use std::fmt::{self, Display, Write};
+
+use rustc_hash::FxHashMap;
+
+use crate::util::grid2d::Grid2D;
+
+type Pos = (usize, usize);
+
+/// Terminal colours usable in overlays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Color {
+    fn ansi_code(self) -> u8 {
+        match self {
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+        }
+    }
+}
+
+/// How an overlay changes the cells it covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    /// Replace the cell with this character.
+    Char(char),
+    /// Keep the cell's character but print it in colour.
+    Color(Color),
+}
+
+/// A printable view of a grid, built by [`Grid2D::render`].
+pub struct Render<'a, T, F> {
+    grid: &'a Grid2D<T>,
+    cell: F,
+    overlays: FxHashMap<Pos, Style>,
+    viewport: Option<(Pos, Pos)>,
+}
+
+impl<T: Display> Display for Grid2D<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> Grid2D<T> {
+    /// Prints each cell as the character returned by `cell`. Overlays and a
+    /// viewport can be added before displaying it.
+    pub fn render<F: Fn(&T) -> char>(&self, cell: F) -> Render<'_, T, F> {
+        Render {
+            grid: self,
+            cell,
+            overlays: FxHashMap::default(),
+            viewport: None,
+        }
+    }
+}
+
+impl<T, F: Fn(&T) -> char> Render<'_, T, F> {
+    /// Applies `style` to every cell in `cells`, e.g. a path. Later overlays
+    /// win over earlier ones.
+    pub fn highlight<I: IntoIterator<Item = Pos>>(mut self, cells: I, style: Style) -> Self {
+        self.overlays
+            .extend(cells.into_iter().map(|pos| (pos, style)));
+        self
+    }
+
+    /// Only shows cells at most `radius` away from `center` on both axes.
+    pub fn viewport(mut self, (x, y): Pos, radius: usize) -> Self {
+        self.viewport = Some((
+            (x.saturating_sub(radius), y.saturating_sub(radius)),
+            (x.saturating_add(radius), y.saturating_add(radius)),
+        ));
+        self
+    }
+}
+
+impl<T, F: Fn(&T) -> char> Display for Render<'_, T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.grid.width == 0 || self.grid.height == 0 {
+            return Ok(());
+        }
+
+        let ((x0, y0), (x1, y1)) = self.viewport.unwrap_or(((0, 0), (usize::MAX, usize::MAX)));
+        let (x1, y1) = (x1.min(self.grid.width - 1), y1.min(self.grid.height - 1));
+
+        for y in y0..=y1 {
+            if y > y0 {
+                writeln!(f)?;
+            }
+            for x in x0..=x1 {
+                let ch = (self.cell)(&self.grid.data[self.grid.index(x, y)]);
+
+                match self.overlays.get(&(x, y)) {
+                    None => f.write_char(ch)?,
+                    Some(Style::Char(c)) => f.write_char(*c)?,
+                    Some(Style::Color(color)) => {
+                        write!(f, "\x1b[{}m{ch}\x1b[0m", color.ansi_code())?
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::grid2d::{
+        Grid2D,
+        display::{Color, Style},
+    };
+
+    #[test]
+    fn display() {
+        let grid = Grid2D::from_fn(3, 2, |x, y| x + y);
+        assert_eq!(grid.to_string(), "012\n123");
+    }
+
+    #[test]
+    fn overlays() {
+        let grid = Grid2D::from_bytes("...\n.#.\n...").unwrap();
+        let render = grid
+            .render(|&b| b as char)
+            .highlight([(0, 0), (1, 0), (2, 0)], Style::Char('o'))
+            .highlight([(2, 0)], Style::Color(Color::Red));
+
+        assert_eq!(render.to_string(), "oo\x1b[31m.\x1b[0m\n.#.\n...");
+    }
+
+    #[test]
+    fn viewport() {
+        let grid = Grid2D::from_fn(10, 10, |x, y| {
+            char::from_digit(((x + y) % 10) as u32, 10).unwrap()
+        });
+
+        let render = grid.render(|&c| c).viewport((1, 8), 1);
+        assert_eq!(render.to_string(), "789\n890\n901");
+
+        let clipped = grid.render(|&c| c).viewport((0, 0), 1);
+        assert_eq!(clipped.to_string(), "01\n12");
+    }
+}
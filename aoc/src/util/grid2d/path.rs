@@ -0,0 +1,257 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+};
+
+use crate::util::grid2d::{Connectivity, Grid2D};
+
+type Pos = (usize, usize);
+
+/// Distances from the source(s) of a search, with enough information to
+/// rebuild a shortest path to any reached cell.
+#[derive(Debug, Clone)]
+pub struct SearchResult<C> {
+    /// Distance of every cell, `None` where it couldn't be reached.
+    pub dist: Grid2D<Option<C>>,
+    prev: Grid2D<Option<Pos>>,
+}
+
+impl<C: Copy> SearchResult<C> {
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            dist: Grid2D::new(width, height, None),
+            prev: Grid2D::new(width, height, None),
+        }
+    }
+
+    pub fn distance(&self, (x, y): Pos) -> Option<C> {
+        self.dist.get(x, y).copied().flatten()
+    }
+
+    /// Shortest path from the closest source to `goal`, both ends included.
+    pub fn path_to(&self, goal: Pos) -> Option<Vec<Pos>> {
+        self.distance(goal)?;
+
+        let mut path = vec![goal];
+        let mut current = goal;
+        while let Some(&Some(prev)) = self.prev.get(current.0, current.1) {
+            path.push(prev);
+            current = prev;
+        }
+        path.reverse();
+
+        Some(path)
+    }
+}
+
+impl<T> Grid2D<T> {
+    /// Breadth-first search from `start`, only stepping on cells for which
+    /// `passable(pos, cell)` holds.
+    pub fn bfs<F>(&self, start: Pos, connectivity: Connectivity, passable: F) -> SearchResult<usize>
+    where
+        F: FnMut(Pos, &T) -> bool,
+    {
+        self.bfs_multi([start], connectivity, passable)
+    }
+
+    /// Like [`Grid2D::bfs`], with every cell of `starts` at distance 0.
+    pub fn bfs_multi<I, F>(
+        &self,
+        starts: I,
+        connectivity: Connectivity,
+        mut passable: F,
+    ) -> SearchResult<usize>
+    where
+        I: IntoIterator<Item = Pos>,
+        F: FnMut(Pos, &T) -> bool,
+    {
+        let mut result = SearchResult::new(self.width, self.height);
+        let mut queue = VecDeque::new();
+
+        for (x, y) in starts {
+            result.dist.data[self.index(x, y)] = Some(0);
+            queue.push_back((x, y));
+        }
+
+        while let Some((x, y)) = queue.pop_front() {
+            let d = result.dist.data[self.index(x, y)].unwrap();
+
+            for ((nx, ny), cell) in self.neighbors(x, y, connectivity) {
+                let idx = self.index(nx, ny);
+                if result.dist.data[idx].is_none() && passable((nx, ny), cell) {
+                    result.dist.data[idx] = Some(d + 1);
+                    result.prev.data[idx] = Some((x, y));
+                    queue.push_back((nx, ny));
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Dijkstra from `start`. `cost(from, to, cell)` is the price of moving
+    /// onto `to`, or `None` if it can't be entered from `from`.
+    pub fn dijkstra<F>(&self, start: Pos, connectivity: Connectivity, cost: F) -> SearchResult<u64>
+    where
+        F: FnMut(Pos, Pos, &T) -> Option<u64>,
+    {
+        self.dijkstra_multi([start], connectivity, cost)
+    }
+
+    /// Like [`Grid2D::dijkstra`], with every cell of `starts` at distance 0.
+    pub fn dijkstra_multi<I, F>(
+        &self,
+        starts: I,
+        connectivity: Connectivity,
+        mut cost: F,
+    ) -> SearchResult<u64>
+    where
+        I: IntoIterator<Item = Pos>,
+        F: FnMut(Pos, Pos, &T) -> Option<u64>,
+    {
+        let mut result = SearchResult::new(self.width, self.height);
+        let mut heap = BinaryHeap::new();
+
+        for (x, y) in starts {
+            result.dist.data[self.index(x, y)] = Some(0);
+            heap.push(Reverse((0, (x, y))));
+        }
+
+        while let Some(Reverse((d, (x, y)))) = heap.pop() {
+            if result.dist.data[self.index(x, y)].is_some_and(|best| d > best) {
+                continue;
+            }
+
+            for ((nx, ny), cell) in self.neighbors(x, y, connectivity) {
+                let Some(step) = cost((x, y), (nx, ny), cell) else {
+                    continue;
+                };
+
+                let idx = self.index(nx, ny);
+                let next = d + step;
+                if result.dist.data[idx].is_none_or(|best| next < best) {
+                    result.dist.data[idx] = Some(next);
+                    result.prev.data[idx] = Some((x, y));
+                    heap.push(Reverse((next, (nx, ny))));
+                }
+            }
+        }
+
+        result
+    }
+
+    /// A* from `start` to `goal`, returning the cost and the path (both ends
+    /// included). `heuristic` must never overestimate the remaining cost.
+    pub fn a_star<F, H>(
+        &self,
+        start: Pos,
+        goal: Pos,
+        connectivity: Connectivity,
+        mut cost: F,
+        mut heuristic: H,
+    ) -> Option<(u64, Vec<Pos>)>
+    where
+        F: FnMut(Pos, Pos, &T) -> Option<u64>,
+        H: FnMut(Pos) -> u64,
+    {
+        let mut result = SearchResult::new(self.width, self.height);
+        let mut heap = BinaryHeap::new();
+
+        result.dist.data[self.index(start.0, start.1)] = Some(0);
+        heap.push(Reverse((heuristic(start), 0, start)));
+
+        while let Some(Reverse((_, d, (x, y)))) = heap.pop() {
+            if (x, y) == goal {
+                return Some((d, result.path_to(goal)?));
+            }
+            if result.dist.data[self.index(x, y)].is_some_and(|best| d > best) {
+                continue;
+            }
+
+            for ((nx, ny), cell) in self.neighbors(x, y, connectivity) {
+                let Some(step) = cost((x, y), (nx, ny), cell) else {
+                    continue;
+                };
+
+                let idx = self.index(nx, ny);
+                let next = d + step;
+                if result.dist.data[idx].is_none_or(|best| next < best) {
+                    result.dist.data[idx] = Some(next);
+                    result.prev.data[idx] = Some((x, y));
+                    heap.push(Reverse((next + heuristic((nx, ny)), next, (nx, ny))));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::grid2d::{Connectivity, Grid2D};
+
+    const MAZE: &str = "\
+S.#.....
+.##.###.
+....#...
+.####.#.
+......#E";
+
+    #[test]
+    fn bfs() {
+        let grid = Grid2D::from_bytes(MAZE).unwrap();
+        let result = grid.bfs((0, 0), Connectivity::Cardinal, |_, &c| c != b'#');
+
+        assert_eq!(result.distance((7, 4)), Some(15));
+        assert_eq!(result.distance((2, 0)), None);
+
+        let path = result.path_to((7, 4)).unwrap();
+        assert_eq!(path.len(), 16);
+        assert_eq!((path[0], path[15]), ((0, 0), (7, 4)));
+        assert!(path.iter().all(|&(x, y)| grid.get(x, y) != Some(&b'#')));
+
+        let diagonal = grid.bfs((0, 0), Connectivity::All, |_, &c| c != b'#');
+        assert_eq!(diagonal.distance((7, 4)), Some(11));
+    }
+
+    #[test]
+    fn bfs_multi() {
+        let grid = Grid2D::from_bytes(MAZE).unwrap();
+        let result = grid.bfs_multi([(0, 0), (7, 4)], Connectivity::Cardinal, |_, &c| c != b'#');
+
+        assert_eq!(result.distance((3, 0)), Some(7));
+        assert_eq!(result.path_to((7, 3)), Some(vec![(7, 4), (7, 3)]));
+    }
+
+    #[test]
+    fn weighted() {
+        let grid = Grid2D::parse_with("19111\n11191", |_, _, c| c.to_digit(10).ok_or(c)).unwrap();
+        let cost = |_, _, &c: &u32| Some(c as u64);
+
+        let result = grid.dijkstra((0, 0), Connectivity::Cardinal, cost);
+        assert_eq!(result.distance((4, 0)), Some(6));
+        assert_eq!(
+            result.path_to((4, 0)),
+            Some(vec![(0, 0), (0, 1), (1, 1), (2, 1), (2, 0), (3, 0), (4, 0)])
+        );
+
+        let manhattan = |(x, y): (usize, usize)| (4 - x + y) as u64;
+        let (total, path) = grid
+            .a_star((0, 0), (4, 0), Connectivity::Cardinal, cost, manhattan)
+            .unwrap();
+        assert_eq!(total, 6);
+        assert_eq!(path.len(), 7);
+
+        assert_eq!(
+            grid.a_star(
+                (0, 0),
+                (4, 0),
+                Connectivity::Cardinal,
+                |_, _, _| None,
+                |_| 0
+            ),
+            None
+        );
+    }
+}
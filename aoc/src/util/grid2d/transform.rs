@@ -0,0 +1,129 @@
+use crate::util::grid2d::Grid2D;
+
+impl<T: Clone> Grid2D<T> {
+    /// Swaps rows and columns, `(x, y)` ends up at `(y, x)`.
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.height, self.width, |x, y| {
+            self.data[self.index(y, x)].clone()
+        })
+    }
+
+    /// Rotates a quarter turn clockwise.
+    pub fn rotate_cw(&self) -> Self {
+        Self::from_fn(self.height, self.width, |x, y| {
+            self.data[self.index(y, self.height - 1 - x)].clone()
+        })
+    }
+
+    /// Rotates a quarter turn counter-clockwise.
+    pub fn rotate_ccw(&self) -> Self {
+        Self::from_fn(self.height, self.width, |x, y| {
+            self.data[self.index(self.width - 1 - y, x)].clone()
+        })
+    }
+
+    /// Mirrors left to right.
+    pub fn flip_horizontal(&self) -> Self {
+        Self::from_fn(self.width, self.height, |x, y| {
+            self.data[self.index(self.width - 1 - x, y)].clone()
+        })
+    }
+
+    /// Mirrors top to bottom.
+    pub fn flip_vertical(&self) -> Self {
+        Self::from_fn(self.width, self.height, |x, y| {
+            self.data[self.index(x, self.height - 1 - y)].clone()
+        })
+    }
+
+    /// Smallest sub-grid containing every cell matching `pred`, or `None` if
+    /// no cell matches.
+    pub fn crop_to<F: FnMut(&T) -> bool>(&self, mut pred: F) -> Option<Self> {
+        let (mut min_x, mut min_y) = (usize::MAX, usize::MAX);
+        let (mut max_x, mut max_y) = (0, 0);
+
+        for ((x, y), cell) in self.iter() {
+            if pred(cell) {
+                min_x = min_x.min(x);
+                min_y = min_y.min(y);
+                max_x = max_x.max(x);
+                max_y = max_y.max(y);
+            }
+        }
+
+        if min_x == usize::MAX {
+            return None;
+        }
+
+        Some(Self::from_fn(
+            max_x - min_x + 1,
+            max_y - min_y + 1,
+            |x, y| self.data[self.index(min_x + x, min_y + y)].clone(),
+        ))
+    }
+
+    /// The distinct grids among the 8 rotations and reflections: the four
+    /// rotations of the grid, then the four rotations of its vertical mirror.
+    pub fn symmetries(&self) -> std::vec::IntoIter<Self>
+    where
+        T: PartialEq,
+    {
+        let mut distinct: Vec<Self> = Vec::with_capacity(8);
+
+        for mut current in [self.clone(), self.flip_vertical()] {
+            for _ in 0..4 {
+                let next = current.rotate_cw();
+                if !distinct.contains(&current) {
+                    distinct.push(current);
+                }
+                current = next;
+            }
+        }
+
+        distinct.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::grid2d::Grid2D;
+
+    fn grid(s: &str) -> Grid2D<u8> {
+        Grid2D::from_bytes(s).unwrap()
+    }
+
+    #[test]
+    fn rotations() {
+        let g = grid("ab\ncd\nef");
+
+        assert_eq!(g.rotate_cw(), grid("eca\nfdb"));
+        assert_eq!(g.rotate_ccw(), grid("bdf\nace"));
+        assert_eq!(g.rotate_cw().rotate_ccw(), g);
+        assert_eq!(g.transpose(), grid("ace\nbdf"));
+    }
+
+    #[test]
+    fn flips() {
+        let g = grid("ab\ncd\nef");
+
+        assert_eq!(g.flip_horizontal(), grid("ba\ndc\nfe"));
+        assert_eq!(g.flip_vertical(), grid("ef\ncd\nab"));
+    }
+
+    #[test]
+    fn crop() {
+        let g = grid(".....\n..#..\n.##..\n.....");
+
+        assert_eq!(g.crop_to(|&c| c == b'#'), Some(grid(".#\n##")));
+        assert_eq!(g.crop_to(|&c| c == b'x'), None);
+    }
+
+    #[test]
+    fn symmetries() {
+        assert_eq!(grid("##\n##").symmetries().count(), 1);
+        assert_eq!(grid("###").symmetries().count(), 2);
+        assert_eq!(grid("#.\n##").symmetries().count(), 4);
+        assert_eq!(grid("##.\n.##").symmetries().count(), 4);
+        assert_eq!(grid("#..\n###").symmetries().count(), 8);
+    }
+}
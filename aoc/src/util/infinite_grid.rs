@@ -0,0 +1,132 @@
+use std::{collections::hash_map, iter::Map};
+
+use rustc_hash::FxHashMap;
+
+use crate::util::{grid2d::Grid2D, point::Point};
+
+/// Sparse grid over signed coordinates, growing as cells are inserted.
+#[derive(Debug, Clone)]
+pub struct InfiniteGrid<T> {
+    cells: FxHashMap<Point, T>,
+    bounds: Option<(Point, Point)>,
+}
+
+impl<T> Default for InfiniteGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: FxHashMap::default(),
+            bounds: None,
+        }
+    }
+}
+
+impl<T> InfiniteGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.cells.get_mut(&p)
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    pub fn insert(&mut self, p: Point, value: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            None => (p, p),
+            Some((min, max)) => (
+                Point::new(min.x.min(p.x), min.y.min(p.y)),
+                Point::new(max.x.max(p.x), max.y.max(p.y)),
+            ),
+        });
+
+        self.cells.insert(p, value)
+    }
+
+    /// Removes a cell. The bounding box doesn't shrink.
+    pub fn remove(&mut self, p: Point) -> Option<T> {
+        self.cells.remove(&p)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Top-left and bottom-right corners (inclusive) of every cell ever
+    /// inserted, `None` while the grid is empty.
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        self.bounds
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.into_iter()
+    }
+}
+
+impl<T: Clone> InfiniteGrid<T> {
+    /// Dense copy of the bounding box, missing cells set to `fill`. Also
+    /// returns the point that ends up at `(0, 0)`.
+    pub fn to_grid(&self, fill: T) -> Option<(Grid2D<T>, Point)> {
+        let (min, max) = self.bounds?;
+        let width = (max.x - min.x + 1) as usize;
+        let height = (max.y - min.y + 1) as usize;
+
+        let grid = Grid2D::from_fn(width, height, |x, y| {
+            self.get(min + Point::from((x, y))).unwrap_or(&fill).clone()
+        });
+
+        Some((grid, min))
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for InfiniteGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        let mut grid = Self::new();
+        for (p, value) in iter {
+            grid.insert(p, value);
+        }
+        grid
+    }
+}
+
+impl<'a, T> IntoIterator for &'a InfiniteGrid<T> {
+    type Item = (Point, &'a T);
+    type IntoIter = Map<hash_map::Iter<'a, Point, T>, fn((&'a Point, &'a T)) -> (Point, &'a T)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cells.iter().map(|(&p, value)| (p, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::{infinite_grid::InfiniteGrid, point::Point};
+
+    #[test]
+    fn grows() {
+        let mut grid: InfiniteGrid<char> = [(Point::new(-2, 1), 'a'), (Point::new(1, -1), 'b')]
+            .into_iter()
+            .collect();
+
+        assert_eq!(grid.bounds(), Some((Point::new(-2, -1), Point::new(1, 1))));
+        assert_eq!(grid.insert(Point::new(1, -1), 'c'), Some('b'));
+        assert_eq!(grid.get(Point::new(-2, 1)), Some(&'a'));
+        assert_eq!(grid.len(), 2);
+
+        let (dense, origin) = grid.to_grid('.').unwrap();
+        assert_eq!(origin, Point::new(-2, -1));
+        assert_eq!(dense.to_string(), "...c\n....\na...");
+
+        assert!(InfiniteGrid::<char>::new().to_grid('.').is_none());
+    }
+}
@@ -0,0 +1,283 @@
+use std::hash::Hash;
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+/// Where a [`Memo`] keeps the values it computed.
+pub trait Storage<K, V> {
+    fn get(&self, key: &K) -> Option<&V>;
+    fn insert(&mut self, key: K, value: V);
+}
+
+impl<K: Hash + Eq, V> Storage<K, V> for FxHashMap<K, V> {
+    fn get(&self, key: &K) -> Option<&V> {
+        FxHashMap::get(self, key)
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        FxHashMap::insert(self, key, value);
+    }
+}
+
+/// Storage for small integer keys, indexing a vector that grows as needed.
+#[derive(Debug, Clone)]
+pub struct Dense<V> {
+    values: Vec<Option<V>>,
+}
+
+impl<V> Dense<V> {
+    pub fn new() -> Self {
+        Self { values: Vec::new() }
+    }
+
+    /// Storage with room for the keys `0..n` before growing.
+    pub fn with_capacity(n: usize) -> Self {
+        Self {
+            values: std::iter::repeat_with(|| None).take(n).collect(),
+        }
+    }
+}
+
+impl<V> Default for Dense<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V> Storage<usize, V> for Dense<V> {
+    fn get(&self, key: &usize) -> Option<&V> {
+        self.values.get(*key)?.as_ref()
+    }
+
+    fn insert(&mut self, key: usize, value: V) {
+        if key >= self.values.len() {
+            self.values.resize_with(key + 1, || None);
+        }
+        self.values[key] = Some(value);
+    }
+}
+
+/// Counters of a [`Memo`], to see how much the cache actually helps.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Stats {
+    /// Lookups answered from the storage.
+    pub hits: usize,
+    /// Lookups of values that were not computed yet.
+    pub misses: usize,
+    /// Calls of the function, restarts included.
+    pub evaluations: usize,
+}
+
+/// Handle given to the function of a [`Memo`] to look up other values.
+pub struct Rec<'a, K, V, S> {
+    storage: &'a S,
+    missing: &'a mut Vec<K>,
+    stats: &'a mut Stats,
+    _value: std::marker::PhantomData<V>,
+}
+
+impl<K, V: Clone, S: Storage<K, V>> Rec<'_, K, V, S> {
+    /// Value of `key` if it is already known. Otherwise `key` is scheduled
+    /// and `None` is returned, which the function should propagate with `?`.
+    ///
+    /// Every miss propagated this way restarts the function, so reading `d`
+    /// unknown values one after the other takes `d` restarts. Use
+    /// [`Rec::get_all`] for a function with many dependencies.
+    pub fn get(&mut self, key: K) -> Option<V> {
+        match self.storage.get(&key) {
+            Some(value) => {
+                self.stats.hits += 1;
+                Some(value.clone())
+            }
+            None => {
+                self.stats.misses += 1;
+                self.missing.push(key);
+                None
+            }
+        }
+    }
+
+    /// Values of all `keys`, in order, if they are all known. Otherwise
+    /// every missing key is scheduled at once and `None` is returned, so
+    /// the function only restarts once.
+    pub fn get_all<I: IntoIterator<Item = K>>(&mut self, keys: I) -> Option<Vec<V>> {
+        let mut values = Vec::new();
+        let mut complete = true;
+
+        for key in keys {
+            match self.get(key) {
+                Some(value) => values.push(value),
+                None => complete = false,
+            }
+        }
+
+        complete.then_some(values)
+    }
+}
+
+/// Memoized recursive function, evaluated with an explicit stack so deep
+/// recursions can't overflow.
+///
+/// The function asks for the values it depends on through [`Rec::get`].
+/// When one of them is not known yet it gives up by returning `None`, and
+/// is run again once the missing values are computed. It should therefore
+/// have no side effects, and do its lookups before any expensive work.
+pub struct Memo<K, V, F, S = FxHashMap<K, V>> {
+    f: F,
+    storage: S,
+    /// Keys that gave up and wait for their dependencies, to catch cycles.
+    waiting: FxHashSet<K>,
+    stats: Stats,
+    _value: std::marker::PhantomData<V>,
+}
+
+impl<K, V, F> Memo<K, V, F>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    F: FnMut(&mut Rec<K, V, FxHashMap<K, V>>, K) -> Option<V>,
+{
+    pub fn new(f: F) -> Self {
+        Self::with_storage(FxHashMap::default(), f)
+    }
+}
+
+impl<K, V, F, S> Memo<K, V, F, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: Storage<K, V>,
+    F: FnMut(&mut Rec<K, V, S>, K) -> Option<V>,
+{
+    pub fn with_storage(storage: S, f: F) -> Self {
+        Self {
+            f,
+            storage,
+            waiting: FxHashSet::default(),
+            stats: Stats::default(),
+            _value: std::marker::PhantomData,
+        }
+    }
+
+    /// Value of the function at `key`, computing whatever it depends on.
+    ///
+    /// # Panics
+    /// If the value of `key` ends up depending on itself.
+    pub fn get(&mut self, key: K) -> V {
+        if let Some(value) = self.storage.get(&key) {
+            self.stats.hits += 1;
+            return value.clone();
+        }
+        self.stats.misses += 1;
+
+        let mut stack = vec![key.clone()];
+        let mut missing = Vec::new();
+
+        while let Some(top) = stack.last() {
+            if self.storage.get(top).is_some() {
+                stack.pop();
+                continue;
+            }
+
+            let top = top.clone();
+            self.stats.evaluations += 1;
+            let mut rec = Rec {
+                storage: &self.storage,
+                missing: &mut missing,
+                stats: &mut self.stats,
+                _value: std::marker::PhantomData,
+            };
+
+            match (self.f)(&mut rec, top.clone()) {
+                Some(value) => {
+                    stack.pop();
+                    self.waiting.remove(&top);
+                    self.storage.insert(top, value);
+                }
+                None => {
+                    assert!(
+                        !missing.is_empty(),
+                        "memoized function returned None without a missing value"
+                    );
+                    self.waiting.insert(top);
+
+                    for dependency in missing.drain(..) {
+                        assert!(
+                            !self.waiting.contains(&dependency),
+                            "cyclic dependency in memoized function"
+                        );
+                        stack.push(dependency);
+                    }
+                }
+            }
+        }
+
+        self.storage.get(&key).unwrap().clone()
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::memo::{Dense, Memo};
+
+    #[test]
+    fn fibonacci() {
+        let mut fib = Memo::with_storage(Dense::new(), |rec, n: usize| {
+            if n < 2 {
+                return Some(n as u64);
+            }
+            Some(rec.get(n - 1)? + rec.get(n - 2)?)
+        });
+
+        assert_eq!(fib.get(90), 2_880_067_194_370_816_120);
+        let stats = fib.stats();
+        // Every value is computed once, after at most one restart
+        assert!(stats.evaluations <= 2 * 91);
+
+        assert_eq!(fib.get(10), 55);
+        assert_eq!(fib.stats().hits, stats.hits + 1);
+        assert_eq!(fib.stats().evaluations, stats.evaluations);
+    }
+
+    #[test]
+    fn many_dependencies() {
+        // Number of ways to climb `n` steps taking 1 to 10 at a time
+        let mut ways = Memo::with_storage(Dense::new(), |rec, n: usize| {
+            if n == 0 {
+                return Some(1u64);
+            }
+            let counts = rec.get_all((1..=n.min(10)).map(|step| n - step))?;
+            Some(counts.iter().sum())
+        });
+
+        assert_eq!(ways.get(4), 8);
+        assert_eq!(ways.get(50), 551_742_150_354_112);
+        // One restart at most per value, however many dependencies
+        assert!(ways.stats().evaluations <= 2 * 51);
+    }
+
+    #[test]
+    fn deep_recursion() {
+        // Far deeper than what the call stack would allow
+        let mut triangle = Memo::new(|rec, n: u64| match n {
+            0 => Some(0),
+            _ => Some(n + rec.get(n - 1)?),
+        });
+
+        assert_eq!(triangle.get(1_000_000), 500_000_500_000);
+    }
+
+    #[test]
+    #[should_panic(expected = "cyclic dependency")]
+    fn cycle() {
+        let mut memo = Memo::new(|rec, n: u32| {
+            let next: u32 = rec.get((n + 1) % 3)?;
+            Some(next + 1)
+        });
+        memo.get(0);
+    }
+}
@@ -0,0 +1,148 @@
+use std::ops::ControlFlow;
+
+use crate::util::dsu::Dsu;
+
+/// Edge of weight `W` between two nodes, stored as `(weight, u, v)`.
+pub type Edge<W> = (W, usize, usize);
+
+/// Kruskal's algorithm over `n` nodes.
+///
+/// Edges are taken by increasing weight, ties broken by their nodes, and
+/// kept when they join two components. After each kept edge, `on_edge` sees
+/// the components right after the merge and can stop the search early.
+/// Returns the kept edges in the order they were added, so the last one is
+/// the one that finally connected everything.
+pub fn kruskal<W, F>(n: usize, mut edges: Vec<Edge<W>>, mut on_edge: F) -> Vec<Edge<W>>
+where
+    W: Ord + Copy,
+    F: FnMut(&mut Dsu, &Edge<W>) -> ControlFlow<()>,
+{
+    edges.sort_unstable();
+
+    let mut dsu = Dsu::new(n);
+    let mut tree = Vec::with_capacity(n.saturating_sub(1));
+
+    for edge in edges {
+        if !dsu.union(edge.1, edge.2) {
+            continue;
+        }
+
+        tree.push(edge);
+        if on_edge(&mut dsu, &edge).is_break() || dsu.component_count() == 1 {
+            break;
+        }
+    }
+
+    tree
+}
+
+/// Prim's algorithm over the complete graph of `n` nodes, in O(n²) time
+/// without building the edge list: `weight(u, v)` is called on demand.
+///
+/// The tree grows from node 0. `on_edge` is called after each added edge
+/// and can stop the search early. Returns the edges in the order they were
+/// added, each as `(weight, node already in the tree, new node)`.
+pub fn prim<W, D, F>(n: usize, mut weight: D, mut on_edge: F) -> Vec<Edge<W>>
+where
+    W: Ord + Copy,
+    D: FnMut(usize, usize) -> W,
+    F: FnMut(&Edge<W>) -> ControlFlow<()>,
+{
+    let mut tree = Vec::with_capacity(n.saturating_sub(1));
+    if n == 0 {
+        return tree;
+    }
+
+    let mut in_tree = vec![false; n];
+    // Cheapest known edge from the tree to every other node
+    let mut best: Vec<Option<(W, usize)>> = vec![None; n];
+    let mut current = 0;
+    in_tree[0] = true;
+
+    for _ in 1..n {
+        for v in 0..n {
+            if in_tree[v] {
+                continue;
+            }
+            let w = weight(current, v);
+            if best[v].is_none_or(|(known, _)| w < known) {
+                best[v] = Some((w, current));
+            }
+        }
+
+        let (next, (w, from)) = best
+            .iter()
+            .enumerate()
+            .filter(|&(v, _)| !in_tree[v])
+            .filter_map(|(v, edge)| Some((v, (*edge)?)))
+            .min_by_key(|&(_, (w, _))| w)
+            .unwrap();
+
+        in_tree[next] = true;
+        current = next;
+
+        let edge = (w, from, next);
+        tree.push(edge);
+        if on_edge(&edge).is_break() {
+            break;
+        }
+    }
+
+    tree
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::ControlFlow;
+
+    use crate::util::mst::{kruskal, prim};
+
+    const POINTS: [(i64, i64); 5] = [(0, 6), (9, 9), (10, 2), (0, 10), (10, 5)];
+
+    fn dist(a: usize, b: usize) -> i64 {
+        (POINTS[a].0 - POINTS[b].0).abs() + (POINTS[a].1 - POINTS[b].1).abs()
+    }
+
+    fn edges() -> Vec<(i64, usize, usize)> {
+        (0..POINTS.len())
+            .flat_map(|u| (u + 1..POINTS.len()).map(move |v| (dist(u, v), u, v)))
+            .collect()
+    }
+
+    #[test]
+    fn same_tree() {
+        let by_kruskal = kruskal(POINTS.len(), edges(), |_, _| ControlFlow::Continue(()));
+        let by_prim = prim(POINTS.len(), dist, |_| ControlFlow::Continue(()));
+
+        assert_eq!(
+            by_kruskal,
+            vec![(3, 2, 4), (4, 0, 3), (5, 1, 4), (10, 1, 3)]
+        );
+        assert_eq!(by_prim, vec![(4, 0, 3), (10, 3, 1), (5, 1, 4), (3, 4, 2)]);
+    }
+
+    #[test]
+    fn stop_early() {
+        let mut sizes = Vec::new();
+        let tree = kruskal(POINTS.len(), edges(), |dsu, _| {
+            sizes.push(dsu.size_of(2));
+            if dsu.component_count() == 3 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+
+        assert_eq!(tree.len(), 2);
+        assert_eq!(sizes, vec![2, 2]);
+
+        let tree = prim(POINTS.len(), dist, |&(w, _, _)| {
+            if w > 5 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        assert_eq!(tree.len(), 2);
+    }
+}
@@ -0,0 +1,210 @@
+use std::ops::{Add, Sub};
+
+use crate::util::{grid2d::Grid2D, point::Point};
+
+/// Summed-area table answering rectangle sums in O(1).
+///
+/// Built either over a dense grid, where coordinates are cell positions, or
+/// over sparse weighted points, where coordinates are compressed to the
+/// distinct `x` and `y` values seen.
+#[derive(Debug, Clone)]
+pub struct PrefixSum2D<T> {
+    /// `sums[(x, y)]` is the sum of every cell strictly above and left of
+    /// `(x, y)`, so it has one more row and column than the source.
+    sums: Grid2D<T>,
+    /// Sorted distinct `x` and `y` values of the points, `None` for a grid.
+    keys: Option<(Vec<i64>, Vec<i64>)>,
+}
+
+impl<T> PrefixSum2D<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    pub fn from_grid(grid: &Grid2D<T>) -> Self {
+        Self {
+            sums: summed_area(grid),
+            keys: None,
+        }
+    }
+
+    /// Points sharing the same position are added together.
+    pub fn from_points<I: IntoIterator<Item = (Point, T)>>(points: I) -> Self {
+        let points: Vec<(Point, T)> = points.into_iter().collect();
+
+        let mut xs: Vec<i64> = points.iter().map(|(p, _)| p.x).collect();
+        let mut ys: Vec<i64> = points.iter().map(|(p, _)| p.y).collect();
+        xs.sort_unstable();
+        xs.dedup();
+        ys.sort_unstable();
+        ys.dedup();
+
+        let mut grid = Grid2D::new(xs.len(), ys.len(), T::default());
+        for (p, value) in points {
+            let x = xs.binary_search(&p.x).unwrap();
+            let y = ys.binary_search(&p.y).unwrap();
+            grid[(x, y)] = grid[(x, y)] + value;
+        }
+
+        Self {
+            sums: summed_area(&grid),
+            keys: Some((xs, ys)),
+        }
+    }
+
+    /// Sum of the rectangle between `min` and `max`, both included. Parts of
+    /// the rectangle outside of the grid count as zero.
+    pub fn sum(&self, min: Point, max: Point) -> T {
+        let (x0, x1, y0, y1) = match &self.keys {
+            None => (
+                clamp(min.x, self.sums.width() - 1),
+                clamp(max.x.saturating_add(1), self.sums.width() - 1),
+                clamp(min.y, self.sums.height() - 1),
+                clamp(max.y.saturating_add(1), self.sums.height() - 1),
+            ),
+            Some((xs, ys)) => (
+                xs.partition_point(|&x| x < min.x),
+                xs.partition_point(|&x| x <= max.x),
+                ys.partition_point(|&y| y < min.y),
+                ys.partition_point(|&y| y <= max.y),
+            ),
+        };
+
+        if x0 >= x1 || y0 >= y1 {
+            return T::default();
+        }
+
+        self.sums[(x1, y1)] - self.sums[(x0, y1)] - self.sums[(x1, y0)] + self.sums[(x0, y0)]
+    }
+}
+
+fn clamp(v: i64, len: usize) -> usize {
+    v.clamp(0, len as i64) as usize
+}
+
+fn summed_area<T>(grid: &Grid2D<T>) -> Grid2D<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    let mut sums = Grid2D::new(grid.width() + 1, grid.height() + 1, T::default());
+
+    for y in 0..grid.height() {
+        for x in 0..grid.width() {
+            sums[(x + 1, y + 1)] =
+                grid[(x, y)] + sums[(x, y + 1)] + sums[(x + 1, y)] - sums[(x, y)];
+        }
+    }
+
+    sums
+}
+
+/// 2D difference array: adds values to whole rectangles in O(1) and
+/// materializes the resulting grid once at the end.
+#[derive(Debug, Clone)]
+pub struct Difference2D<T> {
+    diff: Grid2D<T>,
+}
+
+impl<T> Difference2D<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            diff: Grid2D::new(width + 1, height + 1, T::default()),
+        }
+    }
+
+    /// Adds `value` to every cell between `min` and `max`, both included.
+    /// The rectangle is clipped to the grid.
+    pub fn add(&mut self, min: Point, max: Point, value: T) {
+        let (width, height) = (self.diff.width() - 1, self.diff.height() - 1);
+        let (x0, x1) = (clamp(min.x, width), clamp(max.x.saturating_add(1), width));
+        let (y0, y1) = (clamp(min.y, height), clamp(max.y.saturating_add(1), height));
+
+        if x0 >= x1 || y0 >= y1 {
+            return;
+        }
+
+        self.diff[(x0, y0)] = self.diff[(x0, y0)] + value;
+        self.diff[(x1, y0)] = self.diff[(x1, y0)] - value;
+        self.diff[(x0, y1)] = self.diff[(x0, y1)] - value;
+        self.diff[(x1, y1)] = self.diff[(x1, y1)] + value;
+    }
+
+    pub fn finish(&self) -> Grid2D<T> {
+        let (width, height) = (self.diff.width() - 1, self.diff.height() - 1);
+        let mut grid = Grid2D::new(width, height, T::default());
+
+        for y in 0..height {
+            for x in 0..width {
+                let mut value = self.diff[(x, y)];
+                if x > 0 {
+                    value = value + grid[(x - 1, y)];
+                }
+                if y > 0 {
+                    value = value + grid[(x, y - 1)];
+                }
+                if x > 0 && y > 0 {
+                    value = value - grid[(x - 1, y - 1)];
+                }
+                grid[(x, y)] = value;
+            }
+        }
+
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::{
+        grid2d::Grid2D,
+        point::Point,
+        prefix_sum::{Difference2D, PrefixSum2D},
+    };
+
+    #[test]
+    fn grid_sums() {
+        let grid = Grid2D::from_fn(4, 3, |x, y| (x + 10 * y) as i64);
+        let sums = PrefixSum2D::from_grid(&grid);
+
+        assert_eq!(sums.sum(Point::new(0, 0), Point::new(3, 2)), 138);
+        assert_eq!(sums.sum(Point::new(1, 1), Point::new(2, 2)), 66);
+        assert_eq!(sums.sum(Point::new(-5, 2), Point::new(0, 9)), 20);
+        assert_eq!(sums.sum(Point::new(2, 1), Point::new(1, 2)), 0);
+    }
+
+    #[test]
+    fn sparse_sums() {
+        let sums = PrefixSum2D::from_points([
+            (Point::new(-1_000_000, 5), 1),
+            (Point::new(3, 5), 2),
+            (Point::new(3, 5), 4),
+            (Point::new(700, -8), 8),
+        ]);
+
+        assert_eq!(
+            sums.sum(
+                Point::new(i64::MIN, i64::MIN),
+                Point::new(i64::MAX, i64::MAX)
+            ),
+            15
+        );
+        assert_eq!(sums.sum(Point::new(0, 0), Point::new(1000, 10)), 6);
+        assert_eq!(sums.sum(Point::new(4, -10), Point::new(700, -8)), 8);
+        assert_eq!(sums.sum(Point::new(4, 0), Point::new(699, 10)), 0);
+    }
+
+    #[test]
+    fn range_updates() {
+        let mut diff = Difference2D::new(4, 3);
+        diff.add(Point::new(0, 0), Point::new(1, 1), 1);
+        diff.add(Point::new(1, 1), Point::new(9, 9), 10);
+        diff.add(Point::new(-3, 2), Point::new(0, 2), 100);
+
+        let grid = diff.finish();
+        assert_eq!(grid.row(0), &[1, 1, 0, 0]);
+        assert_eq!(grid.row(1), &[1, 11, 10, 10]);
+        assert_eq!(grid.row(2), &[100, 10, 10, 10]);
+    }
+}
@@ -0,0 +1,725 @@
+use std::{collections::VecDeque, ops::Range};
+
+/// Computes the Z-array of a given string slice `s`.
+///
+/// The Z-array `z` is defined such that `z[i]` is the length of the longest substring
+/// starting at index `i` that matches the prefix of `s`.
+///
+/// # Parameters
+/// - `s`: A byte slice (`&[u8]`) representing the string to process.
+///
+/// # Returns
+/// A vector `Vec<usize>` where each element `z[i]` represents the length of the
+/// longest prefix of `s` that matches the substring starting at position `i`.
+///
+/// # Example
+/// ```
+/// use aoc::util::search::z_algorithm;
+///
+/// let s = b"aaabaaa";
+/// let z = z_algorithm(s);
+/// assert_eq!(z, vec![0, 2, 1, 0, 3, 2, 1]);
+/// ```
+///
+/// # Explanation
+/// The algorithm maintains a window `[l, r]` representing a substring that matches
+/// the prefix. For each position `i`:
+/// 1. If `i` is inside the window, we use previously computed Z-values to avoid redundant comparisons.
+/// 2. Otherwise, we start matching from scratch.
+/// 3. We then expand the window `[l, r]` if the match at `i` goes further than the current `r`.
+///
+/// This implementation runs in O(n) time and O(n) space.
+///
+/// # Applications
+/// - Pattern matching (finding occurrences of a pattern in a string)
+/// - Detecting repeated substrings
+/// - Computing string periodicity
+/// - Solving problems like "is a string composed of repeated blocks?" in linear time
+pub fn z_algorithm(s: &[u8]) -> Vec<usize> {
+    let n = s.len();
+    let mut z = vec![0; n];
+    let (mut l, mut r) = (0, 0);
+
+    for i in 1..n {
+        if i <= r {
+            z[i] = (r - i + 1).min(z[i - l]);
+        }
+        while i + z[i] < n && s[z[i]] == s[i + z[i]] {
+            z[i] += 1;
+        }
+        if i + z[i] - 1 > r {
+            l = i;
+            r = i + z[i] - 1;
+        }
+    }
+
+    z
+}
+
+/// Computes the prefix function of a given string slice `s`.
+///
+/// `pi[i]` is the length of the longest proper prefix of `s[..=i]` that is
+/// also a suffix of it (its longest border).
+///
+/// # Parameters
+/// - `s`: A byte slice (`&[u8]`) representing the string to process.
+///
+/// # Returns
+/// A vector `Vec<usize>` of the same length as `s`.
+///
+/// # Explanation
+/// When `s[..=i]` has a border of length `k`, the next border candidates are
+/// the borders of `s[..k]`, so on a mismatch we fall back to `pi[k - 1]`
+/// instead of restarting. Every fallback undoes an earlier extension, which
+/// makes this O(n) overall.
+///
+/// # Applications
+/// - Knuth-Morris-Pratt pattern matching, see [`find_all`]
+/// - Smallest period of a string, see [`smallest_period`]
+pub fn prefix_function(s: &[u8]) -> Vec<usize> {
+    let mut pi = vec![0; s.len()];
+
+    for i in 1..s.len() {
+        let mut k = pi[i - 1];
+        while k > 0 && s[i] != s[k] {
+            k = pi[k - 1];
+        }
+        if s[i] == s[k] {
+            k += 1;
+        }
+        pi[i] = k;
+    }
+
+    pi
+}
+
+/// Finds every occurrence of `pattern` in `text` with the Knuth-Morris-Pratt
+/// algorithm.
+///
+/// # Returns
+/// The start index of each occurrence, in increasing order. Occurrences may
+/// overlap, and an empty pattern matches at every position from `0` to
+/// `text.len()`.
+///
+/// Runs in O(n + m) time, and only stores the prefix function of `pattern`.
+pub fn find_all(text: &[u8], pattern: &[u8]) -> Vec<usize> {
+    if pattern.is_empty() {
+        return (0..=text.len()).collect();
+    }
+
+    let pi = prefix_function(pattern);
+    let mut matches = Vec::new();
+    let mut k = 0;
+
+    for (i, &c) in text.iter().enumerate() {
+        while k > 0 && c != pattern[k] {
+            k = pi[k - 1];
+        }
+        if c == pattern[k] {
+            k += 1;
+        }
+        if k == pattern.len() {
+            matches.push(i + 1 - k);
+            k = pi[k - 1];
+        }
+    }
+
+    matches
+}
+
+/// Smallest period of `s`: the smallest `p > 0` such that `s[i] == s[i + p]`
+/// for every valid `i`.
+///
+/// The period doesn't have to divide the length, `abcab` has period 3. The
+/// period of an empty string is 0.
+pub fn smallest_period(s: &[u8]) -> usize {
+    match prefix_function(s).last() {
+        Some(&border) => s.len() - border,
+        None => 0,
+    }
+}
+
+/// Tells whether `s` is made of a block repeated at least twice, like `abab`
+/// or `777`.
+///
+/// If `s` is a repetition at all, it is one of the block of length
+/// [`smallest_period`], so checking that one is enough.
+pub fn is_repetition_of_block(s: &[u8]) -> bool {
+    let period = smallest_period(s);
+    period < s.len() && s.len().is_multiple_of(period)
+}
+
+/// Finds the longest palindrome around every center of `s` with Manacher's
+/// algorithm.
+///
+/// # Returns
+/// A vector of length `2 * s.len() + 1`. Entry `c` is the length of the
+/// longest palindrome centered on `s[c / 2]` when `c` is odd, or on the gap
+/// right before `s[c / 2]` when `c` is even. That palindrome is
+/// `s[(c - len) / 2..(c + len) / 2]`.
+///
+/// # Explanation
+/// Centers are scanned left to right while remembering the palindrome that
+/// reaches furthest right. A center inside of it starts from the radius of
+/// its mirror, which is already known, so characters are only compared past
+/// the right edge. This runs in O(n) time.
+pub fn manacher(s: &[u8]) -> Vec<usize> {
+    // Character at position `j` of `s` with a gap inserted around every byte
+    let at = |j: usize| (j % 2 == 1).then(|| s[j / 2]);
+    let m = 2 * s.len() + 1;
+    let mut radius = vec![0; m];
+    let (mut center, mut right) = (0, 0);
+
+    for j in 0..m {
+        let mut k = if j < right {
+            radius[2 * center - j].min(right - j)
+        } else {
+            0
+        };
+        while k < j && j + k + 1 < m && at(j - k - 1) == at(j + k + 1) {
+            k += 1;
+        }
+        radius[j] = k;
+
+        if j + k > right {
+            center = j;
+            right = j + k;
+        }
+    }
+
+    radius
+}
+
+/// Range of the longest palindromic substring of `s`, the leftmost one on
+/// ties. See [`manacher`].
+pub fn longest_palindrome(s: &[u8]) -> Range<usize> {
+    let (c, &len) = manacher(s)
+        .iter()
+        .enumerate()
+        .rev()
+        .max_by_key(|&(_, len)| len)
+        .unwrap();
+
+    (c - len) / 2..(c + len) / 2
+}
+
+/// Polynomial hashes of every prefix of a string, to compare substrings in
+/// O(1).
+///
+/// Hashes are taken modulo the Mersenne prime 2⁶¹ - 1 with a fixed base, so
+/// ranges of two different `RollingHash` can be compared too. Different
+/// substrings collide with a probability around `n / 2⁶¹`.
+#[derive(Debug, Clone)]
+pub struct RollingHash {
+    /// `prefix[i]` is the hash of `s[..i]`.
+    prefix: Vec<u64>,
+    /// `powers[i]` is `BASE^i`.
+    powers: Vec<u64>,
+}
+
+impl RollingHash {
+    const MOD: u64 = (1 << 61) - 1;
+    const BASE: u64 = 0x1f3d_5b79_a6c2_e435 % Self::MOD;
+
+    pub fn new(s: &[u8]) -> Self {
+        let mut prefix = Vec::with_capacity(s.len() + 1);
+        let mut powers = Vec::with_capacity(s.len() + 1);
+        prefix.push(0);
+        powers.push(1);
+
+        for (i, &c) in s.iter().enumerate() {
+            // Shifted by one so that leading zero bytes still change the hash
+            prefix.push((Self::mul(prefix[i], Self::BASE) + c as u64 + 1) % Self::MOD);
+            powers.push(Self::mul(powers[i], Self::BASE));
+        }
+
+        Self { prefix, powers }
+    }
+
+    fn mul(a: u64, b: u64) -> u64 {
+        let product = a as u128 * b as u128;
+        ((product >> 61) as u64 + (product as u64 & Self::MOD)) % Self::MOD
+    }
+
+    /// Length of the hashed string.
+    pub fn len(&self) -> usize {
+        self.prefix.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Hash of the substring in `range`.
+    pub fn hash(&self, range: Range<usize>) -> u64 {
+        let removed = Self::mul(self.prefix[range.start], self.powers[range.len()]);
+        (self.prefix[range.end] + Self::MOD - removed) % Self::MOD
+    }
+
+    /// Tells whether the substrings in ranges `a` and `b` are equal, up to
+    /// hash collisions.
+    pub fn substr_eq(&self, a: Range<usize>, b: Range<usize>) -> bool {
+        a.len() == b.len() && self.hash(a) == self.hash(b)
+    }
+
+    /// Length of the longest common prefix of the suffixes starting at `i`
+    /// and `j`, by binary search over the hashes in O(log n). Panics if
+    /// either is past the end.
+    pub fn common_prefix(&self, i: usize, j: usize) -> usize {
+        assert!(
+            i.max(j) <= self.len(),
+            "Suffix {} out of bounds of length {}",
+            i.max(j),
+            self.len()
+        );
+        let (mut lo, mut hi) = (0, self.len() - i.max(j));
+        while lo < hi {
+            let mid = (lo + hi).div_ceil(2);
+            if self.hash(i..i + mid) == self.hash(j..j + mid) {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+        lo
+    }
+}
+
+/// Occurrence of one of the patterns of an [`AhoCorasick`] automaton.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Match {
+    /// Index of the pattern, in the order they were given.
+    pub pattern: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Aho-Corasick automaton, finding occurrences of many patterns in a single
+/// pass over the text.
+///
+/// Bytes are first mapped to a compact alphabet of the bytes that appear in
+/// the patterns, so the full transition table stays small.
+#[derive(Debug, Clone)]
+pub struct AhoCorasick {
+    /// Class of every byte, 0 for the bytes that no pattern contains.
+    classes: [usize; 256],
+    alphabet: usize,
+    /// `next[node * alphabet + class]`, already following failure links.
+    next: Vec<usize>,
+    /// Patterns ending exactly at each node.
+    outputs: Vec<Vec<usize>>,
+    /// Closest node along the failure links that has outputs.
+    output_link: Vec<Option<usize>>,
+    lengths: Vec<usize>,
+}
+
+impl AhoCorasick {
+    /// Builds the automaton in O(total length × alphabet). Empty patterns
+    /// never match.
+    pub fn new<I>(patterns: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        let patterns: Vec<I::Item> = patterns.into_iter().collect();
+
+        let mut classes = [0; 256];
+        let mut alphabet = 1;
+        for &c in patterns.iter().flat_map(|p| p.as_ref()) {
+            if classes[c as usize] == 0 {
+                classes[c as usize] = alphabet;
+                alphabet += 1;
+            }
+        }
+
+        // Trie of the patterns, 0 standing for a missing child
+        let mut next = vec![0; alphabet];
+        let mut outputs = vec![Vec::new()];
+        for (id, pattern) in patterns.iter().enumerate() {
+            let pattern = pattern.as_ref();
+            if pattern.is_empty() {
+                continue;
+            }
+
+            let mut node = 0;
+            for &c in pattern {
+                let slot = node * alphabet + classes[c as usize];
+                if next[slot] == 0 {
+                    next[slot] = outputs.len();
+                    next.extend(std::iter::repeat_n(0, alphabet));
+                    outputs.push(Vec::new());
+                }
+                node = next[slot];
+            }
+            outputs[node].push(id);
+        }
+
+        // Failure links in BFS order, so the transitions of a node's failure
+        // link are complete when the node itself is filled in
+        let nodes = outputs.len();
+        let mut fail = vec![0; nodes];
+        let mut output_link = vec![None; nodes];
+        let mut queue: VecDeque<usize> =
+            (0..alphabet).map(|c| next[c]).filter(|&v| v != 0).collect();
+
+        while let Some(u) = queue.pop_front() {
+            for c in 0..alphabet {
+                let v = next[u * alphabet + c];
+                let fallback = next[fail[u] * alphabet + c];
+                if v == 0 {
+                    next[u * alphabet + c] = fallback;
+                    continue;
+                }
+
+                fail[v] = fallback;
+                output_link[v] = if outputs[fallback].is_empty() {
+                    output_link[fallback]
+                } else {
+                    Some(fallback)
+                };
+                queue.push_back(v);
+            }
+        }
+
+        Self {
+            classes,
+            alphabet,
+            next,
+            outputs,
+            output_link,
+            lengths: patterns.iter().map(|p| p.as_ref().len()).collect(),
+        }
+    }
+
+    /// Every occurrence of every pattern in `text`, overlapping ones
+    /// included, ordered by end position. Runs in O(n + number of matches).
+    pub fn find_all(&self, text: &[u8]) -> Vec<Match> {
+        let mut matches = Vec::new();
+        let mut node = 0;
+
+        for (i, &c) in text.iter().enumerate() {
+            node = self.next[node * self.alphabet + self.classes[c as usize]];
+
+            let mut current = if self.outputs[node].is_empty() {
+                self.output_link[node]
+            } else {
+                Some(node)
+            };
+            while let Some(n) = current {
+                matches.extend(self.outputs[n].iter().map(|&pattern| Match {
+                    pattern,
+                    start: i + 1 - self.lengths[pattern],
+                    end: i + 1,
+                }));
+                current = self.output_link[n];
+            }
+        }
+
+        matches
+    }
+}
+
+/// Computes the suffix array of a given string slice `s`.
+///
+/// # Parameters
+/// - `s`: A byte slice (`&[u8]`) representing the string to process.
+///
+/// # Returns
+/// The start index of every suffix of `s`, in lexicographic order of the
+/// suffixes.
+///
+/// # Explanation
+/// Prefix doubling: once suffixes are ranked by their first `k` bytes, the
+/// rank of their first `2k` bytes is the pair of ranks of `s[i..]` and
+/// `s[i + k..]`. Those pairs are radix sorted in O(n), and there are at
+/// most log n rounds, so this runs in O(n log n) time.
+///
+/// # Applications
+/// - Longest repeated substring, see [`longest_repeated_substring`]
+/// - Number of distinct substrings, see [`count_distinct_substrings`]
+/// - Longest common substring, see [`longest_common_substring`]
+pub fn suffix_array(s: &[u8]) -> Vec<usize> {
+    sort_suffixes(s.iter().map(|&c| c as usize).collect())
+}
+
+/// Suffix array of a string given as the rank of each of its characters.
+fn sort_suffixes(mut rank: Vec<usize>) -> Vec<usize> {
+    let n = rank.len();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let mut sa: Vec<usize> = (0..n).collect();
+    sa.sort_by_key(|&i| rank[i]);
+    let mut next_rank = vec![0; n];
+    let mut k = 1;
+
+    loop {
+        // By second half first: suffixes too short to have one come first,
+        // then the others in the order of their second half
+        let by_second: Vec<usize> = (n.saturating_sub(k)..n)
+            .chain(sa.iter().filter(|&&i| i >= k).map(|&i| i - k))
+            .collect();
+
+        // Then stable counting sort by first half
+        let mut start = vec![0; rank.iter().max().unwrap() + 2];
+        for &i in &by_second {
+            start[rank[i] + 1] += 1;
+        }
+        for r in 1..start.len() {
+            start[r] += start[r - 1];
+        }
+        for &i in &by_second {
+            sa[start[rank[i]]] = i;
+            start[rank[i]] += 1;
+        }
+
+        next_rank[sa[0]] = 0;
+        for w in 1..n {
+            let (a, b) = (sa[w - 1], sa[w]);
+            let same = rank[a] == rank[b] && rank.get(a + k) == rank.get(b + k);
+            next_rank[b] = next_rank[a] + usize::from(!same);
+        }
+        std::mem::swap(&mut rank, &mut next_rank);
+
+        if rank[sa[n - 1]] == n - 1 {
+            break;
+        }
+        k *= 2;
+    }
+
+    sa
+}
+
+/// Computes the LCP array of `s` from its suffix array `sa` with Kasai's
+/// algorithm.
+///
+/// # Returns
+/// A vector where `lcp[i]` is the length of the longest common prefix of
+/// the suffixes `sa[i - 1]` and `sa[i]`, and `lcp[0]` is 0.
+///
+/// # Explanation
+/// Suffixes are visited in text order. If `s[i..]` shares `h` bytes with the
+/// suffix before it in `sa`, then `s[i + 1..]` shares at least `h - 1` with
+/// its own predecessor, so the comparison resumes from there. `h` decreases
+/// at most n times in total, which makes this O(n).
+pub fn lcp_array<T: Eq>(s: &[T], sa: &[usize]) -> Vec<usize> {
+    let n = s.len();
+    let mut position = vec![0; n];
+    for (p, &i) in sa.iter().enumerate() {
+        position[i] = p;
+    }
+
+    let mut lcp = vec![0; n];
+    let mut h: usize = 0;
+    for i in 0..n {
+        if position[i] == 0 {
+            h = 0;
+            continue;
+        }
+
+        let j = sa[position[i] - 1];
+        while i + h < n && j + h < n && s[i + h] == s[j + h] {
+            h += 1;
+        }
+        lcp[position[i]] = h;
+        h = h.saturating_sub(1);
+    }
+
+    lcp
+}
+
+/// Range of the longest substring occurring at least twice in `s`, both
+/// occurrences being allowed to overlap. Empty when no byte repeats.
+pub fn longest_repeated_substring(s: &[u8]) -> Range<usize> {
+    let sa = suffix_array(s);
+    let lcp = lcp_array(s, &sa);
+
+    match lcp.iter().enumerate().max_by_key(|&(_, len)| len) {
+        Some((p, &len)) if len > 0 => sa[p]..sa[p] + len,
+        _ => 0..0,
+    }
+}
+
+/// Number of distinct non-empty substrings of `s`.
+///
+/// Every substring is a prefix of a suffix, and the prefixes a suffix
+/// shares with the one before it in the suffix array were already counted.
+pub fn count_distinct_substrings(s: &[u8]) -> usize {
+    let sa = suffix_array(s);
+    let n = s.len();
+
+    n * (n + 1) / 2 - lcp_array(s, &sa).iter().sum::<usize>()
+}
+
+/// Range in `a` of the longest substring that also occurs in `b`. Empty
+/// when they have no byte in common.
+///
+/// # Explanation
+/// Builds the suffix array of `a`, a separator smaller than any byte, then
+/// `b`. The longest common substring is the longest common prefix of some
+/// suffix of `a` and some suffix of `b`, and the best such pair is always
+/// next to each other in the suffix array. The separator keeps common
+/// prefixes from running from `a` into `b`.
+pub fn longest_common_substring(a: &[u8], b: &[u8]) -> Range<usize> {
+    let joined: Vec<usize> = a
+        .iter()
+        .map(|&c| c as usize + 1)
+        .chain([0])
+        .chain(b.iter().map(|&c| c as usize + 1))
+        .collect();
+
+    let sa = sort_suffixes(joined.clone());
+    let lcp = lcp_array(&joined, &sa);
+    let in_a = |i: usize| i < a.len();
+
+    (1..sa.len())
+        .filter(|&p| in_a(sa[p - 1]) != in_a(sa[p]))
+        .max_by_key(|&p| lcp[p])
+        .filter(|&p| lcp[p] > 0)
+        .map_or(0..0, |p| {
+            let start = if in_a(sa[p]) { sa[p] } else { sa[p - 1] };
+            start..start + lcp[p]
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::search::{
+        AhoCorasick, Match, RollingHash, count_distinct_substrings, find_all,
+        is_repetition_of_block, lcp_array, longest_common_substring, longest_palindrome,
+        longest_repeated_substring, manacher, prefix_function, smallest_period, suffix_array,
+        z_algorithm,
+    };
+
+    #[test]
+    fn z_and_prefix_function() {
+        assert_eq!(z_algorithm(b"aaabaaa"), vec![0, 2, 1, 0, 3, 2, 1]);
+        assert_eq!(prefix_function(b"aabaaab"), vec![0, 1, 0, 1, 2, 2, 3]);
+    }
+
+    #[test]
+    fn kmp() {
+        assert_eq!(find_all(b"abababa", b"aba"), vec![0, 2, 4]);
+        assert_eq!(find_all(b"abc", b"d"), vec![]);
+        assert_eq!(find_all(b"ab", b""), vec![0, 1, 2]);
+
+        let text = b"the cat sat on the mat with the other cat";
+        for pattern in [&b"the"[..], b"at", b"t", b"cats"] {
+            let naive: Vec<usize> = (0..=text.len() - pattern.len())
+                .filter(|&i| text[i..].starts_with(pattern))
+                .collect();
+            assert_eq!(find_all(text, pattern), naive);
+        }
+    }
+
+    #[test]
+    fn periods() {
+        assert_eq!(smallest_period(b""), 0);
+        assert_eq!(smallest_period(b"abcab"), 3);
+        assert_eq!(smallest_period(b"abcd"), 4);
+        assert_eq!(smallest_period(b"121212"), 2);
+
+        assert!(is_repetition_of_block(b"777"));
+        assert!(is_repetition_of_block(b"123123"));
+        assert!(!is_repetition_of_block(b"7"));
+        assert!(!is_repetition_of_block(b"12312"));
+    }
+
+    #[test]
+    fn palindromes() {
+        let s = b"abacabbx";
+        let radius = manacher(s);
+        assert_eq!(radius.len(), 2 * s.len() + 1);
+        // "bacab" around the 'c', "bb" around the gap before the second 'b'
+        assert_eq!(radius[7], 5);
+        assert_eq!(radius[12], 2);
+
+        assert_eq!(longest_palindrome(s), 1..6);
+        assert_eq!(longest_palindrome(b"xyzzyw"), 1..5);
+        assert_eq!(longest_palindrome(b""), 0..0);
+    }
+
+    #[test]
+    fn rolling_hash() {
+        let hash = RollingHash::new(b"abcabcabd");
+        assert!(hash.substr_eq(0..3, 3..6));
+        assert!(!hash.substr_eq(3..6, 6..9));
+        assert!(!hash.substr_eq(0..3, 0..4));
+        assert_eq!(hash.common_prefix(0, 3), 5);
+        assert_eq!(hash.common_prefix(1, 2), 0);
+        assert_eq!(hash.common_prefix(9, 2), 0);
+
+        let other = RollingHash::new(b"xxcabc");
+        assert_eq!(hash.hash(2..6), other.hash(2..6));
+        assert_ne!(
+            RollingHash::new(b"\0a").hash(0..2),
+            RollingHash::new(b"a").hash(0..1)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Suffix 10 out of bounds of length 9")]
+    fn common_prefix_out_of_bounds() {
+        RollingHash::new(b"abcabcabd").common_prefix(0, 10);
+    }
+
+    #[test]
+    fn aho_corasick() {
+        let patterns = ["he", "she", "his", "hers", ""];
+        let automaton = AhoCorasick::new(patterns);
+        let text = b"ushers and his hers";
+
+        let matches = automaton.find_all(text);
+        let mut naive: Vec<Match> = (0..text.len())
+            .flat_map(|start| {
+                patterns.iter().enumerate().filter_map(move |(pattern, p)| {
+                    (!p.is_empty() && text[start..].starts_with(p.as_bytes())).then_some(Match {
+                        pattern,
+                        start,
+                        end: start + p.len(),
+                    })
+                })
+            })
+            .collect();
+        naive.sort_by_key(|m| (m.end, m.start));
+
+        let mut sorted = matches.clone();
+        sorted.sort_by_key(|m| (m.end, m.start));
+        assert_eq!(sorted, naive);
+        assert!(matches.is_sorted_by_key(|m| m.end));
+        assert_eq!(matches.len(), 6);
+    }
+
+    #[test]
+    fn suffixes() {
+        let s = b"banana";
+        let sa = suffix_array(s);
+        assert_eq!(sa, vec![5, 3, 1, 0, 4, 2]);
+        assert_eq!(lcp_array(s, &sa), vec![0, 1, 3, 0, 0, 2]);
+
+        assert!(suffix_array(b"").is_empty());
+        assert_eq!(suffix_array(b"aaaa"), vec![3, 2, 1, 0]);
+
+        let text = b"mississippi river banks";
+        let mut naive: Vec<usize> = (0..text.len()).collect();
+        naive.sort_by_key(|&i| &text[i..]);
+        assert_eq!(suffix_array(text), naive);
+    }
+
+    #[test]
+    fn substrings() {
+        assert_eq!(longest_repeated_substring(b"banana"), 1..4);
+        assert_eq!(longest_repeated_substring(b"abc"), 0..0);
+
+        assert_eq!(count_distinct_substrings(b"abab"), 7);
+        assert_eq!(count_distinct_substrings(b"aaa"), 3);
+        assert_eq!(count_distinct_substrings(b""), 0);
+
+        let a = b"xabcdey";
+        assert_eq!(longest_common_substring(a, b"zzbcdezz"), 2..6);
+        assert_eq!(longest_common_substring(a, b"qrs"), 0..0);
+        assert_eq!(longest_common_substring(b"", a), 0..0);
+    }
+}
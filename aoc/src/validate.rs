@@ -0,0 +1,75 @@
+use crate::Diagnostic;
+
+/// Lines of the trimmed input along with their 1-based line number in the
+/// original input, since solvers ignore leading and trailing blank lines.
+pub fn numbered_lines(input: &str) -> Vec<(usize, &str)> {
+    let trimmed = input.trim();
+    let skipped = input[..input.len() - input.trim_start().len()]
+        .matches('\n')
+        .count();
+
+    if trimmed.is_empty() {
+        return Vec::new();
+    }
+
+    trimmed
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (skipped + i + 1, line))
+        .collect()
+}
+
+/// Checks that the input is a non-empty rectangular grid made of `allowed` characters.
+pub fn check_grid(input: &str, allowed: &str) -> Vec<Diagnostic> {
+    let lines = numbered_lines(input);
+    let Some(&(_, first)) = lines.first() else {
+        return vec![Diagnostic::global("input is empty")];
+    };
+
+    let width = first.chars().count();
+    let mut diagnostics = Vec::new();
+
+    for &(n, line) in &lines {
+        let line_width = line.chars().count();
+        if line_width != width {
+            diagnostics.push(Diagnostic::at(
+                n,
+                format!("expected {width} columns like the first line, found {line_width}"),
+            ));
+        }
+
+        if let Some((col, ch)) = line
+            .chars()
+            .enumerate()
+            .find(|&(_, c)| !allowed.contains(c))
+        {
+            diagnostics.push(Diagnostic::at(
+                n,
+                format!("unexpected character {ch:?} at column {}", col + 1),
+            ));
+        }
+    }
+
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Diagnostic, validate::check_grid};
+
+    #[test]
+    fn grid() {
+        assert_eq!(check_grid("\n..@\n@@.\n", ".@"), vec![]);
+        assert_eq!(
+            check_grid("\n..@\n@@\n@x.", ".@"),
+            vec![
+                Diagnostic::at(3, "expected 3 columns like the first line, found 2"),
+                Diagnostic::at(4, "unexpected character 'x' at column 2"),
+            ]
+        );
+        assert_eq!(
+            check_grid("  \n", "."),
+            vec![Diagnostic::global("input is empty")]
+        );
+    }
+}
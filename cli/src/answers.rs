@@ -0,0 +1,115 @@
+use std::{collections::BTreeMap, fs, time::Duration};
+
+const ANSWERS_PATH: &str = "inputs/answers.tsv";
+
+/// Last result recorded for one implementation of a part.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub input_hash: u64,
+    pub answer: String,
+    pub elapsed: Duration,
+}
+
+/// Saved answers and timings, one tab-separated line per
+/// `(day, part, implementation)`:
+/// `day  part  implementation  input_hash  nanos  answer`
+#[derive(Debug, Default)]
+pub struct Answers {
+    records: BTreeMap<(u8, u8, String), Record>,
+}
+
+impl Answers {
+    pub fn load() -> Self {
+        let Ok(content) = fs::read_to_string(ANSWERS_PATH) else {
+            return Self::default();
+        };
+
+        let records = content
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.splitn(6, '\t');
+                let day = fields.next()?.parse().ok()?;
+                let part = fields.next()?.parse().ok()?;
+                let implementation = fields.next()?.to_string();
+                let input_hash = u64::from_str_radix(fields.next()?, 16).ok()?;
+                let elapsed = Duration::from_nanos(fields.next()?.parse().ok()?);
+                let answer = unescape(fields.next()?);
+
+                Some((
+                    (day, part, implementation),
+                    Record {
+                        input_hash,
+                        answer,
+                        elapsed,
+                    },
+                ))
+            })
+            .collect();
+
+        Self { records }
+    }
+
+    pub fn get(&self, day: u8, part: u8, implementation: &str) -> Option<&Record> {
+        self.records.get(&(day, part, implementation.to_string()))
+    }
+
+    pub fn insert(&mut self, day: u8, part: u8, implementation: &str, record: Record) {
+        self.records
+            .insert((day, part, implementation.to_string()), record);
+    }
+
+    pub fn save(&self) {
+        let content: String = self
+            .records
+            .iter()
+            .map(|((day, part, implementation), r)| {
+                format!(
+                    "{day}\t{part}\t{implementation}\t{:016x}\t{}\t{}\n",
+                    r.input_hash,
+                    r.elapsed.as_nanos(),
+                    escape(&r.answer)
+                )
+            })
+            .collect();
+
+        fs::write(ANSWERS_PATH, content).expect("Failed to write answers file");
+    }
+}
+
+/// Escapes backslashes and newlines so an answer fits on one line.
+fn escape(answer: &str) -> String {
+    answer.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+/// Inverse of [`escape`].
+fn unescape(field: &str) -> String {
+    let mut answer = String::with_capacity(field.len());
+    let mut chars = field.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                answer.push('\n');
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                answer.push('\\');
+                chars.next();
+            }
+            _ => answer.push(c),
+        }
+    }
+    answer
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{escape, unescape};
+
+    #[test]
+    fn escape_round_trip() {
+        for answer in ["42", "a\nb", "a\\nb", "\\", "a\\\nb\\", "\n\n"] {
+            assert!(!escape(answer).contains('\n'));
+            assert_eq!(unescape(&escape(answer)), answer);
+        }
+    }
+}
@@ -1,6 +1,15 @@
+mod answers;
+mod profile;
+
+use answers::{Answers, Record};
 use aoc::fmt_duration;
 use clap::{Parser, Subcommand};
-use std::{fs, path::Path, process::Command, time::Instant};
+use std::{
+    fs, panic,
+    path::Path,
+    process::{self, Command},
+    time::{Duration, Instant},
+};
 
 #[derive(Parser, Debug)]
 struct Args {
@@ -17,42 +26,272 @@ enum CommandEnum {
         day: u8,
         #[arg(short, long)]
         part: Option<u8>,
+        #[arg(short, long = "impl", default_value = aoc::DEFAULT_IMPL)]
+        implementation: String,
+        /// Record the answer even if it differs from the one recorded for this input
+        #[arg(long)]
+        accept: bool,
+    },
+    Compare {
+        day: u8,
+    },
+    Validate {
+        day: u8,
+    },
+    Profile {
+        day: u8,
+        #[arg(short, long)]
+        part: u8,
+        #[arg(short, long = "impl", default_value = aoc::DEFAULT_IMPL)]
+        implementation: String,
+        /// Stop after this many runs (default: only stop after --duration)
+        #[arg(short = 'n', long)]
+        iterations: Option<u64>,
+        /// Maximum time spent in the loop, in seconds
+        #[arg(short, long, default_value_t = 10.0)]
+        duration: f64,
+    },
+    Stress {
+        day: u8,
+        #[arg(short = 'n', long, default_value_t = 1000)]
+        iterations: u64,
+        #[arg(short, long, default_value_t = 20)]
+        size: usize,
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
     },
     Generate {
         day: u8,
     },
 }
 
-fn run_part(solver: &dyn aoc::Day, input: &str, part_num: u8) {
+fn run_part(
+    solver: &dyn aoc::Day,
+    day: u8,
+    input: &str,
+    part_num: u8,
+    implementation: &str,
+    accept: bool,
+    answers: &mut Answers,
+) {
+    if !(1..=2).contains(&part_num) {
+        eprintln!("Invalid part {part_num}");
+        return;
+    }
+
     let start = Instant::now();
-    let result = match part_num {
-        1 => solver.part1(input),
-        2 => solver.part2(input),
-        _ => {
-            eprintln!("Invalid part {part_num}");
-            return;
-        }
+    let Some(result) = solver.solve(part_num, implementation, input) else {
+        eprintln!(
+            "No implementation {implementation} for part {part_num} (available: {})",
+            solver.all_implementations(part_num).join(", ")
+        );
+        return;
     };
     let elapsed = start.elapsed();
     println!("Part {part_num}: {result} (🚀 {})", fmt_duration(elapsed));
+
+    let record = Record {
+        input_hash: aoc::fingerprint(input),
+        answer: result.to_string(),
+        elapsed,
+    };
+
+    if let Some(previous) = answers.get(day, part_num, implementation) {
+        if previous.input_hash != record.input_hash {
+            println!(
+                "  ⚠️ Input changed since the last recorded answer ({}, 🚀 {})",
+                previous.answer,
+                fmt_duration(previous.elapsed)
+            );
+        } else if previous.answer != record.answer {
+            println!(
+                "  ⚠️ Answer differs from the one recorded for this input ({})",
+                previous.answer
+            );
+            if !accept {
+                println!("  Keeping the recorded answer, pass --accept to replace it");
+                return;
+            }
+        }
+    }
+
+    answers.insert(day, part_num, implementation, record);
 }
 
-fn run_day(day: u8, part: Option<u8>) {
-    let solver = match aoc::DAYS.get(&day) {
-        Some(s) => s.as_ref(),
-        None => {
-            eprintln!("No solution found for day {day}");
-            return;
+fn get_solver(day: u8) -> Option<&'static dyn aoc::Day> {
+    let solver = aoc::DAYS.get(&day).map(|s| s.as_ref() as &dyn aoc::Day);
+    if solver.is_none() {
+        eprintln!("No solution found for day {day}");
+    }
+    solver
+}
+
+fn run_day(day: u8, part: Option<u8>, implementation: &str, accept: bool) {
+    let Some(solver) = get_solver(day) else {
+        return;
+    };
+
+    let input = aoc::load_input(day);
+    let mut answers = Answers::load();
+
+    let parts = match part {
+        Some(p) => vec![p],
+        None => vec![1, 2],
+    };
+    for p in parts {
+        run_part(solver, day, &input, p, implementation, accept, &mut answers);
+    }
+
+    answers.save();
+}
+
+fn compare(day: u8) {
+    let Some(solver) = get_solver(day) else {
+        return;
+    };
+
+    let input = aoc::load_input(day);
+    let mut all_agree = true;
+
+    for part in 1..=2 {
+        let names = solver.implementations(part);
+        let mut results = Vec::with_capacity(names.len());
+
+        for name in names {
+            let start = Instant::now();
+            let result = solver.solve(part, name, &input).unwrap();
+            results.push((name, result, start.elapsed()));
+        }
+
+        let (_, reference, reference_time) = &results[0];
+        for (name, result, elapsed) in &results {
+            let ratio = elapsed.as_secs_f64() / reference_time.as_secs_f64();
+            println!(
+                "Part {part} [{name}]: {result} (🚀 {}, x{ratio:.2})",
+                fmt_duration(*elapsed)
+            );
+        }
+
+        if results.iter().all(|(_, result, _)| result == reference) {
+            println!("Part {part}: {} implementation(s) agree ✅", results.len());
+        } else {
+            println!("Part {part}: implementations disagree ❌");
+            all_agree = false;
         }
+    }
+
+    if !all_agree {
+        process::exit(1);
+    }
+}
+
+fn validate(day: u8) {
+    let Some(solver) = get_solver(day) else {
+        return;
     };
 
     let input = aoc::load_input(day);
+    let Some(diagnostics) = solver.validate(&input) else {
+        println!("Day {day:02} has no validator");
+        return;
+    };
+
+    for diagnostic in &diagnostics {
+        println!("{diagnostic}");
+    }
 
-    if let Some(p) = part {
-        run_part(solver, &input, p);
+    if diagnostics.is_empty() {
+        println!("No issues found in day {day:02} input ✅");
     } else {
-        run_part(solver, &input, 1);
-        run_part(solver, &input, 2);
+        println!(
+            "{} issue(s) found in day {day:02} input ❌",
+            diagnostics.len()
+        );
+    }
+}
+
+fn run_profile(day: u8, part: u8, implementation: &str, iterations: Option<u64>, duration: f64) {
+    let Some(solver) = get_solver(day) else {
+        return;
+    };
+
+    if !(1..=2).contains(&part) {
+        eprintln!("Invalid part {part}");
+        return;
+    }
+
+    if !solver.all_implementations(part).contains(&implementation) {
+        eprintln!("No implementation {implementation} for part {part}");
+        return;
+    }
+
+    let Ok(duration) = Duration::try_from_secs_f64(duration) else {
+        eprintln!("Invalid duration {duration}");
+        return;
+    };
+
+    let input = aoc::load_input(day);
+    let samples = profile::sample(solver, part, implementation, &input, iterations, duration);
+
+    if samples.is_empty() {
+        eprintln!("No iteration completed");
+        return;
+    }
+
+    profile::report(samples);
+}
+
+fn stress(day: u8, iterations: u64, max_size: usize, base_seed: u64) {
+    let Some(solver) = get_solver(day) else {
+        return;
+    };
+
+    if solver.generate_input(base_seed, 1).is_none() {
+        eprintln!("Day {day} has no input generator");
+        return;
+    }
+    if (1..=2).all(|part| solver.all_implementations(part).len() < 2) {
+        eprintln!("Day {day} has no variants to cross-check against");
+        return;
+    }
+
+    // Panics are reported as mismatches, don't let the hook spam stderr
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let start = Instant::now();
+    let mut failure = None;
+
+    for i in 0..iterations {
+        let seed = base_seed.wrapping_add(i);
+        let size = 1 + (i as usize % max_size.max(1));
+
+        if let Some(mismatch) = aoc::stress::check(solver, seed, size) {
+            println!("Mismatch on seed {seed} (size {size}), looking for a smaller one...");
+            failure = Some(aoc::stress::find_smaller_failure(solver, mismatch));
+            break;
+        }
+    }
+
+    panic::set_hook(default_hook);
+
+    match failure {
+        None => println!(
+            "{iterations} generated inputs agree ✅ ({})",
+            fmt_duration(start.elapsed())
+        ),
+        Some(m) => {
+            let show = |outcome: &aoc::stress::Outcome| match outcome {
+                Ok(solution) => solution.to_string(),
+                Err(msg) => format!("panicked: {msg}"),
+            };
+
+            println!("Part {} [{}] disagrees ❌", m.part, m.implementation);
+            println!("  seed: {}, size: {}", m.seed, m.size);
+            println!("  {}: {}", aoc::DEFAULT_IMPL, show(&m.expected));
+            println!("  {}: {}", m.implementation, show(&m.found));
+            println!("Input:\n{}", m.input);
+        }
     }
 }
 
@@ -90,7 +329,7 @@ fn generate(day: u8) {
     }
 
     let template = format!(
-        r#"use crate::{{Day, Solution}};
+        r#"use crate::util::prelude::*;
 
 pub struct Day{num:02};
 
@@ -127,7 +366,27 @@ fn main() {
 
     match args.command {
         CommandEnum::Test { day } => run_tests(day),
-        CommandEnum::Run { day, part } => run_day(day, part),
+        CommandEnum::Run {
+            day,
+            part,
+            implementation,
+            accept,
+        } => run_day(day, part, &implementation, accept),
+        CommandEnum::Compare { day } => compare(day),
+        CommandEnum::Validate { day } => validate(day),
+        CommandEnum::Profile {
+            day,
+            part,
+            implementation,
+            iterations,
+            duration,
+        } => run_profile(day, part, &implementation, iterations, duration),
+        CommandEnum::Stress {
+            day,
+            iterations,
+            size,
+            seed,
+        } => stress(day, iterations, size, seed),
         CommandEnum::Generate { day } => generate(day),
     }
 }
@@ -0,0 +1,83 @@
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use aoc::fmt_duration;
+
+const BUCKETS: usize = 12;
+const BAR_WIDTH: usize = 40;
+/// Most samples preallocated up front, however many iterations are asked for.
+const MAX_PREALLOCATED: u64 = 1 << 16;
+
+/// Runs one part in a tight loop until `iterations` runs are done or
+/// `duration` has elapsed, printing nothing so an external sampler only sees
+/// the solver. Returns every iteration's latency.
+pub fn sample(
+    solver: &dyn aoc::Day,
+    part: u8,
+    implementation: &str,
+    input: &str,
+    iterations: Option<u64>,
+    duration: Duration,
+) -> Vec<Duration> {
+    let mut samples = Vec::with_capacity(iterations.unwrap_or(1024).min(MAX_PREALLOCATED) as usize);
+    let start = Instant::now();
+
+    while iterations.is_none_or(|n| (samples.len() as u64) < n) && start.elapsed() < duration {
+        let iteration = Instant::now();
+        black_box(solver.solve(part, implementation, black_box(input)));
+        samples.push(iteration.elapsed());
+    }
+
+    samples
+}
+
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    let rank = ((sorted.len() - 1) as f64 * p).round() as usize;
+    sorted[rank]
+}
+
+/// Prints min/p50/p99/max and a histogram of the latencies, with
+/// logarithmic buckets so a few slow outliers don't squash the rest.
+pub fn report(mut samples: Vec<Duration>) {
+    samples.sort_unstable();
+
+    let total: Duration = samples.iter().sum();
+    let (min, max) = (samples[0], samples[samples.len() - 1]);
+
+    println!(
+        "{} iterations in {} (mean {})",
+        samples.len(),
+        fmt_duration(total),
+        fmt_duration(total / samples.len() as u32)
+    );
+    println!(
+        "min {} | p50 {} | p99 {} | max {}",
+        fmt_duration(min),
+        fmt_duration(percentile(&samples, 0.50)),
+        fmt_duration(percentile(&samples, 0.99)),
+        fmt_duration(max)
+    );
+
+    let lo = min.as_secs_f64().max(1e-9);
+    let ratio = (max.as_secs_f64().max(lo) / lo).powf(1.0 / BUCKETS as f64);
+
+    let mut counts = [0usize; BUCKETS];
+    for sample in &samples {
+        let bucket = if ratio > 1.0 {
+            (sample.as_secs_f64().max(lo) / lo).log(ratio) as usize
+        } else {
+            0
+        };
+        counts[bucket.min(BUCKETS - 1)] += 1;
+    }
+
+    let largest = *counts.iter().max().unwrap();
+    for (i, &count) in counts.iter().enumerate() {
+        let bound = Duration::from_secs_f64(lo * ratio.powi(i as i32 + 1));
+        let bar = "█".repeat(count * BAR_WIDTH / largest);
+
+        println!("  ≤ {:>10} {bar:<BAR_WIDTH$} {count}", fmt_duration(bound));
+    }
+}